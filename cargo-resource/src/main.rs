@@ -0,0 +1,441 @@
+//! `cargo resource` checks the files loaded by the `resource` macros in a
+//! crate, reporting missing files, unused files in asset directories,
+//! duplicate content, and the embedded size of each directory.
+//!
+//! Paths are resolved with the same code as the macros themselves, so the
+//! results match what the build does. Files generated by build scripts aren't
+//! checked, as they only exist during the build.
+//!
+//! ```text
+//! cargo resource [--manifest-path <Cargo.toml>] [--dir <asset dir>]...
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use resource_common::paths::{self, Base};
+
+use crate::scan::{Found, Invocation};
+
+mod scan;
+
+const USAGE: &str = "\
+Checks the files loaded by the `resource` macros in a crate.
+
+Usage: cargo resource [OPTIONS]
+
+Options:
+  --manifest-path <PATH>  Path to the Cargo.toml of the crate to check
+  --dir <DIR>             An asset directory to check for unused files, relative
+                          to the crate root. Can be repeated. Defaults to
+                          `assets`, if it exists.
+  -h, --help              Print this message";
+
+struct Options {
+    manifest_dir: PathBuf,
+    asset_dirs: Vec<PathBuf>,
+}
+
+/// A file loaded by one of the macros.
+struct LoadedFile {
+    /// The path of the file relative to the workspace root, for display.
+    display: String,
+    size: u64,
+    hash: u64,
+}
+
+#[derive(Default)]
+struct Report {
+    missing: Vec<String>,
+    skipped: Vec<String>,
+    invalid: Vec<String>,
+    loaded: BTreeMap<PathBuf, LoadedFile>,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options() {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&options) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_options() -> Result<Option<Options>, String> {
+    let mut args = std::env::args().skip(1).peekable();
+
+    // Cargo passes the subcommand name as the first argument
+    if args.peek().is_some_and(|arg| arg == "resource") {
+        args.next();
+    }
+
+    let mut manifest_path = None;
+    let mut asset_dirs = vec![];
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("`{}` requires a value", name))
+        };
+        match arg.as_str() {
+            "--manifest-path" => manifest_path = Some(PathBuf::from(value(&arg)?)),
+            "--dir" => asset_dirs.push(PathBuf::from(value(&arg)?)),
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let manifest_dir = match manifest_path {
+        Some(path) => path
+            .canonicalize()
+            .map_err(|e| format!("couldn't read `{}`: {}", path.display(), e))?
+            .parent()
+            .map(Path::to_owned)
+            .ok_or("the manifest path has no parent directory")?,
+        None => find_manifest_dir()?,
+    };
+
+    if asset_dirs.is_empty() && manifest_dir.join("assets").is_dir() {
+        asset_dirs.push(PathBuf::from("assets"));
+    }
+
+    Ok(Some(Options {
+        manifest_dir,
+        asset_dirs,
+    }))
+}
+
+fn find_manifest_dir() -> Result<PathBuf, String> {
+    let current_dir = std::env::current_dir()
+        .map_err(|e| format!("couldn't read the current directory: {}", e))?;
+    current_dir
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_owned)
+        .ok_or_else(|| "couldn't find Cargo.toml in this directory or any parent".to_owned())
+}
+
+/// Checks the crate and prints the report, returning `false` if there were
+/// any errors.
+fn run(options: &Options) -> Result<bool, String> {
+    let manifest_dir = &options.manifest_dir;
+    let workspace_root = paths::workspace_root(manifest_dir)?;
+
+    let mut report = Report::default();
+    for source_file in scan::source_files(manifest_dir)? {
+        for found in scan::scan_file(&source_file)? {
+            check_invocation(manifest_dir, &workspace_root, found, &mut report);
+        }
+    }
+
+    print_section("Missing files", &report.missing);
+    print_section("Invalid macro invocations", &report.invalid);
+    print_section("Not checked", &report.skipped);
+    print_section(
+        "Unused files",
+        &unused_files(options, &workspace_root, &report)?,
+    );
+    print_section("Duplicate content", &duplicates(&report));
+    print_section("Embedded size by directory", &sizes(&report));
+
+    Ok(report.missing.is_empty() && report.invalid.is_empty())
+}
+
+fn check_invocation(manifest_dir: &Path, workspace_root: &Path, found: Found, report: &mut Report) {
+    let location = format!(
+        "{}:{}",
+        relative_display(workspace_root, &found.file),
+        found.line
+    );
+
+    let invocation = match found.invocation {
+        Ok(invocation) => invocation,
+        Err(e) => {
+            report.invalid.push(format!("{}: {}", location, e));
+            return;
+        }
+    };
+
+    match invocation {
+        Invocation::Files(files) => {
+            for file in files {
+                let name = &file.path.value;
+                let root = match (&file.package, file.base) {
+                    (Some(package), _) => paths::package_dir(manifest_dir, &package.to_string()),
+                    (None, Base::OutDir) => {
+                        report.skipped.push(format!(
+                            "{}: `out_dir: {}` is generated by the build script",
+                            location, name
+                        ));
+                        continue;
+                    }
+                    (None, _) => Ok(manifest_dir.to_owned()),
+                };
+
+                let result = root.and_then(|root| {
+                    let path = root.join(name);
+                    if path.is_file() {
+                        Ok(path)
+                    } else {
+                        Err(format!("`{}` does not exist", path.display()))
+                    }
+                });
+                match result {
+                    Ok(path) => add_loaded(workspace_root, &path, report),
+                    Err(e) => report.missing.push(format!("{}: {}", location, e)),
+                }
+            }
+        }
+        Invocation::List {
+            base,
+            options,
+            path,
+        } => {
+            if base == Base::OutDir {
+                report.skipped.push(format!(
+                    "{}: `{}` is generated by the build script",
+                    location, path.value
+                ));
+                return;
+            }
+
+            let files = paths::resolve_dir(manifest_dir, base, &path.value)
+                .and_then(|dir| paths::enumerate_files_paths(&dir, options));
+            match files {
+                Ok(files) => {
+                    for (_, path) in files {
+                        add_loaded(workspace_root, &manifest_dir.join(path), report);
+                    }
+                }
+                Err(e) => report.missing.push(format!("{}: {}", location, e)),
+            }
+        }
+    }
+}
+
+fn add_loaded(workspace_root: &Path, path: &Path, report: &mut Report) {
+    let Ok(canonical) = path.canonicalize() else {
+        report
+            .missing
+            .push(format!("`{}` could not be resolved", path.display()));
+        return;
+    };
+    if report.loaded.contains_key(&canonical) {
+        return;
+    }
+
+    match std::fs::read(&canonical) {
+        Ok(data) => {
+            report.loaded.insert(
+                canonical.clone(),
+                LoadedFile {
+                    display: relative_display(workspace_root, &canonical),
+                    size: data.len() as u64,
+                    hash: resource_common::fnv1a(&data),
+                },
+            );
+        }
+        Err(e) => report
+            .missing
+            .push(format!("couldn't read `{}`: {}", path.display(), e)),
+    }
+}
+
+/// Lists the files in the asset directories which aren't loaded by any macro.
+fn unused_files(
+    options: &Options,
+    workspace_root: &Path,
+    report: &Report,
+) -> Result<Vec<String>, String> {
+    let mut unused = BTreeSet::new();
+    for dir in &options.asset_dirs {
+        let dir = paths::ResolvedDir {
+            dir: options.manifest_dir.join(dir),
+            relative: dir.clone(),
+        };
+        let list_options = paths::ListOptions {
+            recursive: true,
+            follow_symlinks: false,
+        };
+
+        for (_, path) in paths::enumerate_files_paths(&dir, list_options)? {
+            let path = options.manifest_dir.join(path);
+            let loaded = path
+                .canonicalize()
+                .is_ok_and(|canonical| report.loaded.contains_key(&canonical));
+            if !loaded {
+                unused.insert(relative_display(workspace_root, &path));
+            }
+        }
+    }
+
+    Ok(unused.into_iter().collect())
+}
+
+/// Lists groups of loaded files with identical contents.
+fn duplicates(report: &Report) -> Vec<String> {
+    let mut by_content: BTreeMap<(u64, u64), Vec<&str>> = BTreeMap::new();
+    for file in report.loaded.values() {
+        by_content
+            .entry((file.size, file.hash))
+            .or_default()
+            .push(&file.display);
+    }
+
+    by_content
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|((size, _), files)| format!("{} ({} bytes each)", files.join(", "), size))
+        .collect()
+}
+
+/// Sums the sizes of the loaded files in each directory. Files loaded by
+/// several macros are only counted once.
+fn sizes(report: &Report) -> Vec<String> {
+    let mut by_dir: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
+    for file in report.loaded.values() {
+        let dir = file.display.rsplit_once('/').map_or(".", |(dir, _)| dir);
+        let (count, size) = by_dir.entry(dir).or_default();
+        *count += 1;
+        *size += file.size;
+    }
+
+    let total: u64 = by_dir.values().map(|(_, size)| size).sum();
+    let mut lines: Vec<String> = by_dir
+        .into_iter()
+        .map(|(dir, (count, size))| format!("{}: {} bytes in {}", dir, size, files(count)))
+        .collect();
+    if !lines.is_empty() {
+        lines.push(format!(
+            "total: {} bytes in {}",
+            total,
+            files(report.loaded.len())
+        ));
+    }
+    lines
+}
+
+fn files(count: usize) -> String {
+    match count {
+        1 => "1 file".to_owned(),
+        count => format!("{} files", count),
+    }
+}
+
+fn relative_display(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn print_section(title: &str, lines: &[String]) {
+    if lines.is_empty() {
+        return;
+    }
+
+    println!("{}:", title);
+    for line in lines {
+        println!("    {}", line);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loaded(files: &[(&str, u64, u64)]) -> Report {
+        let mut report = Report::default();
+        for &(display, size, hash) in files {
+            report.loaded.insert(
+                PathBuf::from(display),
+                LoadedFile {
+                    display: display.to_owned(),
+                    size,
+                    hash,
+                },
+            );
+        }
+        report
+    }
+
+    #[test]
+    fn reports_unused_files() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let options = Options {
+            manifest_dir: root.to_owned(),
+            asset_dirs: vec![PathBuf::from("tests/resource_list_test_files")],
+        };
+
+        let mut report = Report::default();
+        add_loaded(
+            root,
+            &root.join("tests/resource_list_test_files/file_b.txt"),
+            &mut report,
+        );
+
+        assert!(report.missing.is_empty());
+        assert_eq!(
+            unused_files(&options, root, &report).unwrap(),
+            [
+                "tests/resource_list_test_files/file_a.txt",
+                "tests/resource_list_test_files/file_c.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_content() {
+        let report = loaded(&[
+            ("assets/a.txt", 4, 1),
+            ("assets/b.txt", 4, 2),
+            ("assets/copy/a.txt", 4, 1),
+            ("assets/empty.txt", 0, 1),
+        ]);
+
+        assert_eq!(
+            duplicates(&report),
+            ["assets/a.txt, assets/copy/a.txt (4 bytes each)"]
+        );
+    }
+
+    #[test]
+    fn reports_sizes_by_directory() {
+        let report = loaded(&[
+            ("assets/a.txt", 4, 1),
+            ("assets/b.txt", 6, 2),
+            ("assets/copy/a.txt", 4, 1),
+            ("build.rs", 100, 3),
+        ]);
+
+        assert_eq!(
+            sizes(&report),
+            [
+                ".: 100 bytes in 1 file",
+                "assets: 10 bytes in 2 files",
+                "assets/copy: 4 bytes in 1 file",
+                "total: 114 bytes in 4 files",
+            ]
+        );
+        assert!(sizes(&Report::default()).is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{TokenStream, TokenTree};
+use resource_common::{
+    args::{Args, FileArg, PathArg},
+    paths::{Base, ListOptions},
+};
+use syn::{
+    Token,
+    parse::{ParseStream, Parser},
+    punctuated::Punctuated,
+    token::{Bracket, Paren},
+};
+
+const SINGLE_FILE_MACROS: &[&str] = &[
+    "resource",
+    "resource_str",
+    "resource_static",
+    "resource_str_static",
+    "resource_reader",
+    "resource_async",
+];
+
+const LIST_MACROS: &[&str] = &["resource_list", "resource_str_list"];
+
+/// The arguments of a macro invocation, parsed the same way the macro does.
+pub enum Invocation {
+    /// One of the single file macros, loading one or more files.
+    Files(Vec<FileArg>),
+
+    /// One of the list macros, loading every file in a directory.
+    List {
+        base: Base,
+        options: ListOptions,
+        path: PathArg,
+    },
+}
+
+/// A macro invocation found in a source file.
+pub struct Found {
+    pub file: PathBuf,
+    pub line: usize,
+    pub invocation: syn::Result<Invocation>,
+}
+
+/// Returns the Rust source files of a crate, skipping hidden directories, the
+/// `target` directory, and any nested crates.
+pub fn source_files(manifest_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = vec![];
+    visit_dir(manifest_dir, manifest_dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn visit_dir(manifest_dir: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if dir != manifest_dir && (dir.join("Cargo.toml").is_file() || dir.ends_with("target")) {
+        return Ok(());
+    }
+
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory `{}`: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        if entry.file_name().as_encoded_bytes().starts_with(b".") {
+            continue;
+        }
+
+        let path = entry.path();
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to read file type of `{}`: {}", path.display(), e))?;
+        if file_type.is_dir() {
+            visit_dir(manifest_dir, &path, files)?;
+        } else if file_type.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the invocations of the `resource` macros in a source file,
+/// including those nested inside other macros.
+pub fn scan_file(path: &Path) -> Result<Vec<Found>, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read `{}`: {}", path.display(), e))?;
+    let tokens: TokenStream = source
+        .parse()
+        .map_err(|e| format!("Failed to parse `{}`: {}", path.display(), e))?;
+
+    let mut found = vec![];
+    scan_tokens(path, tokens, &mut found);
+    Ok(found)
+}
+
+fn scan_tokens(path: &Path, tokens: TokenStream, found: &mut Vec<Found>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for (i, token) in tokens.iter().enumerate() {
+        let TokenTree::Group(group) = token else {
+            continue;
+        };
+
+        if let [TokenTree::Ident(name), TokenTree::Punct(bang), _] =
+            &tokens[i.saturating_sub(2)..=i]
+            && bang.as_char() == '!'
+            && !is_macro_definition(group.stream())
+        {
+            let name = name.to_string();
+            let invocation = if SINGLE_FILE_MACROS.contains(&name.as_str()) {
+                Some(parse_files.parse2(group.stream()).map(Invocation::Files))
+            } else if LIST_MACROS.contains(&name.as_str()) {
+                Some(parse_list(group.stream()))
+            } else {
+                None
+            };
+
+            if let Some(invocation) = invocation {
+                found.push(Found {
+                    file: path.to_owned(),
+                    line: tokens[i - 2].span().start().line,
+                    invocation,
+                });
+                continue;
+            }
+        }
+
+        scan_tokens(path, group.stream(), found);
+    }
+}
+
+/// Returns `true` for arguments containing `macro_rules!` metavariables, which
+/// can't be resolved without expanding the surrounding macro.
+fn is_macro_definition(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Punct(punct) => punct.as_char() == '$',
+        TokenTree::Group(group) => is_macro_definition(group.stream()),
+        _ => false,
+    })
+}
+
+fn parse_files(input: ParseStream) -> syn::Result<Vec<FileArg>> {
+    // Arrays prefixed with `try` return a `Result` instead of panicking
+    if input.peek(Token![try]) && input.peek2(Bracket) {
+        input.parse::<Token![try]>()?;
+    }
+
+    let files = if input.peek(Bracket) || input.peek(Paren) {
+        let content;
+        if input.peek(Bracket) {
+            syn::bracketed!(content in input);
+        } else {
+            syn::parenthesized!(content in input);
+        }
+        Punctuated::<PathArg, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .map(|path| FileArg {
+                base: Base::Manifest,
+                package: None,
+                path,
+            })
+            .collect()
+    } else {
+        vec![input.parse()?]
+    };
+
+    // Skip the transformation function, if any
+    input.parse::<TokenStream>()?;
+    Ok(files)
+}
+
+fn parse_list(tokens: TokenStream) -> syn::Result<Invocation> {
+    let mut args: Args = syn::parse2(tokens)?;
+    args.take_crate_path()?;
+    let base = args.take_base()?;
+    let options = ListOptions {
+        recursive: args.take_flag("recursive")?,
+        follow_symlinks: args.take_flag("follow_symlinks")?,
+    };
+    args.take_flag("try")?;
+    let path = args.finish()?;
+
+    Ok(Invocation::List {
+        base,
+        options,
+        path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(source: &str) -> Vec<Found> {
+        let mut found = vec![];
+        scan_tokens(Path::new("main.rs"), source.parse().unwrap(), &mut found);
+        found
+    }
+
+    fn file_paths(found: &Found) -> Vec<String> {
+        match &found.invocation {
+            Ok(Invocation::Files(files)) => {
+                files.iter().map(|file| file.path.value.clone()).collect()
+            }
+            _ => panic!("Expected a single file macro"),
+        }
+    }
+
+    #[test]
+    fn finds_single_file_macros() {
+        let found = scan(
+            r#"
+            static LAZY: LazyResource<str> = resource_str_static!("a.txt");
+
+            fn main() {
+                let b = resource::resource!("b.bin", decode);
+                let [c, d] = resource_str!(["c.txt", "d.txt"]);
+                let e = resource_str!(package assets, "e.txt");
+            }
+            "#,
+        );
+
+        assert_eq!(found.len(), 4);
+        assert_eq!(found[0].line, 2);
+        assert_eq!(file_paths(&found[0]), ["a.txt"]);
+        assert_eq!(file_paths(&found[1]), ["b.bin"]);
+        assert_eq!(file_paths(&found[2]), ["c.txt", "d.txt"]);
+        assert_eq!(file_paths(&found[3]), ["e.txt"]);
+    }
+
+    #[test]
+    fn finds_try_arrays() {
+        let found = scan(r#"let [a, b] = resource!(try ["a.bin", "b.bin"]).unwrap();"#);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(file_paths(&found[0]), ["a.bin", "b.bin"]);
+    }
+
+    #[test]
+    fn finds_macros_nested_in_other_macros() {
+        let found = scan(r#"println!("{}", resource_str!(concat!("a", ".txt")));"#);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(file_paths(&found[0]), ["a.txt"]);
+    }
+
+    #[test]
+    fn finds_list_macros() {
+        let found = scan(
+            r#"let files = resource_list!("assets", relative_to = workspace, recursive = true);"#,
+        );
+
+        match &found[..] {
+            [
+                Found {
+                    invocation:
+                        Ok(Invocation::List {
+                            base: Base::Workspace,
+                            options:
+                                ListOptions {
+                                    recursive: true,
+                                    follow_symlinks: false,
+                                },
+                            path,
+                        }),
+                    ..
+                },
+            ] => assert_eq!(path.value, "assets"),
+            _ => panic!("Expected a list macro"),
+        }
+    }
+
+    #[test]
+    fn finds_try_list_macros() {
+        let found = scan(r#"let files = resource_str_list!("assets", try = true).unwrap();"#);
+
+        match &found[..] {
+            [
+                Found {
+                    invocation: Ok(Invocation::List { path, .. }),
+                    ..
+                },
+            ] => assert_eq!(path.value, "assets"),
+            _ => panic!("Expected a list macro"),
+        }
+    }
+
+    #[test]
+    fn skips_macro_definitions() {
+        let found = scan(
+            r#"
+            macro_rules! load {
+                ($name:tt) => { resource_str!($name) };
+            }
+            "#,
+        );
+
+        assert!(found.is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+use proc_macro2::Span;
+use syn::{
+    Expr, ExprLit, Ident, Lit, LitStr, Macro, Path, Token,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+use crate::paths::Base;
+
+/// A path given to one of the macros, either as a string literal or as a
+/// `concat!` of literals.
+pub struct PathArg {
+    pub value: String,
+    pub span: Span,
+}
+
+impl Parse for PathArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let literal: LitStr = input.parse()?;
+            return Ok(PathArg {
+                value: literal.value(),
+                span: literal.span(),
+            });
+        }
+
+        if input.peek(Ident) && input.peek2(Token![!]) {
+            let mac: Macro = input.parse()?;
+            if mac.path.is_ident("concat") {
+                let span = mac.delimiter.span().join();
+                let parts = mac.parse_body_with(Punctuated::<Lit, Token![,]>::parse_terminated)?;
+                let value = parts
+                    .iter()
+                    .map(concat_literal)
+                    .collect::<syn::Result<String>>()?;
+                return Ok(PathArg { value, span });
+            }
+            return Err(syn::Error::new_spanned(
+                mac.path,
+                "expected a string literal or `concat!`",
+            ));
+        }
+
+        Err(input.error("expected a string literal"))
+    }
+}
+
+fn concat_literal(literal: &Lit) -> syn::Result<String> {
+    match literal {
+        Lit::Str(s) => Ok(s.value()),
+        Lit::Char(c) => Ok(c.value().to_string()),
+        Lit::Int(i) => Ok(i.base10_digits().to_owned()),
+        Lit::Float(f) => Ok(f.base10_digits().to_owned()),
+        Lit::Bool(b) => Ok(b.value.to_string()),
+        _ => Err(syn::Error::new_spanned(
+            literal,
+            "cannot concatenate this literal",
+        )),
+    }
+}
+
+/// A `key = value` option following the path.
+pub struct OptionArg {
+    pub key: Ident,
+    pub value: Expr,
+}
+
+impl Parse for OptionArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `parse_any` so that keywords like `crate` can be used as keys
+        let key = Ident::parse_any(input)?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(OptionArg { key, value })
+    }
+}
+
+/// The arguments to a macro: a path optionally followed by comma-separated
+/// `key = value` options.
+pub struct Args {
+    pub path: PathArg,
+    options: Vec<OptionArg>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+
+        let mut options = vec![];
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let option: OptionArg = input.parse()?;
+            if options
+                .iter()
+                .any(|existing: &OptionArg| existing.key == option.key)
+            {
+                return Err(syn::Error::new_spanned(
+                    &option.key,
+                    format!("duplicate option `{}`", option.key),
+                ));
+            }
+            options.push(option);
+        }
+
+        Ok(Args { path, options })
+    }
+}
+
+impl Args {
+    /// Removes and returns the option with the given key, if present.
+    pub fn take_option(&mut self, key: &str) -> Option<OptionArg> {
+        let index = self.options.iter().position(|option| option.key == key)?;
+        Some(self.options.remove(index))
+    }
+
+    /// Removes and returns the `crate = path` option, or the path to the
+    /// `resource` crate if it isn't present.
+    pub fn take_crate_path(&mut self) -> syn::Result<Path> {
+        match self.take_option("crate") {
+            Some(OptionArg {
+                value: Expr::Path(path),
+                ..
+            }) if path.qself.is_none() => Ok(path.path),
+            Some(option) => Err(syn::Error::new_spanned(
+                option.value,
+                "expected a path to the `resource` crate",
+            )),
+            None => Ok(syn::parse_quote!(::resource)),
+        }
+    }
+
+    /// Removes and returns the `relative_to = base` option, or the crate root
+    /// if it isn't present.
+    pub fn take_base(&mut self) -> syn::Result<Base> {
+        let Some(option) = self.take_option("relative_to") else {
+            return Ok(Base::Manifest);
+        };
+
+        let base = match &option.value {
+            Expr::Path(path) if path.qself.is_none() => path
+                .path
+                .get_ident()
+                .and_then(|ident| Base::from_name(&ident.to_string())),
+            _ => None,
+        };
+
+        base.ok_or_else(|| {
+            syn::Error::new_spanned(
+                option.value,
+                "expected `manifest`, `workspace` or `out_dir`",
+            )
+        })
+    }
+
+    /// Removes and returns a `key = true` or `key = false` option, or `false`
+    /// if it isn't present.
+    pub fn take_flag(&mut self, key: &str) -> syn::Result<bool> {
+        match self.take_option(key) {
+            Some(OptionArg {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Bool(flag),
+                        ..
+                    }),
+                ..
+            }) => Ok(flag.value),
+            Some(option) => Err(syn::Error::new_spanned(
+                option.value,
+                "expected `true` or `false`",
+            )),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns an error for the first option which hasn't been taken.
+    pub fn finish(self) -> syn::Result<PathArg> {
+        match self.options.first() {
+            Some(option) => Err(syn::Error::new_spanned(
+                &option.key,
+                format!("unknown option `{}`", option.key),
+            )),
+            None => Ok(self.path),
+        }
+    }
+}
+
+/// The argument to the single file macros: a path, optionally prefixed with
+/// `out_dir:` for files generated by a build script, or with `package name,`
+/// for files in another crate in the workspace.
+pub struct FileArg {
+    pub base: Base,
+    pub package: Option<Ident>,
+    pub path: PathArg,
+}
+
+impl Parse for FileArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut base = Base::Manifest;
+        let mut package = None;
+        if input.peek(Ident) && input.peek2(Ident) {
+            let prefix: Ident = input.parse()?;
+            if prefix != "package" {
+                return Err(syn::Error::new_spanned(prefix, "expected `package`"));
+            }
+            package = Some(input.parse()?);
+            input.parse::<Token![,]>()?;
+        } else if input.peek(Ident) && input.peek2(Token![:]) {
+            let prefix: Ident = input.parse()?;
+            if prefix != "out_dir" {
+                return Err(syn::Error::new_spanned(prefix, "expected `out_dir`"));
+            }
+            input.parse::<Token![:]>()?;
+            base = Base::OutDir;
+        }
+
+        let path = input.parse()?;
+        Ok(FileArg {
+            base,
+            package,
+            path,
+        })
+    }
+}
@@ -0,0 +1,14 @@
+//! Argument parsing, path resolution and hashing shared by the `resource` macros and
+//! `cargo resource`, so that both resolve paths exactly the same way.
+//!
+//! This is an implementation detail, with no stable API.
+
+pub mod args;
+pub mod paths;
+
+/// The 64-bit FNV-1a hash, used for the content hashes in asset manifests.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,796 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Component, Path, PathBuf},
+    process::Command,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// The directory that paths given to the macros are relative to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    /// The root of the crate invoking the macro. This is the default.
+    Manifest,
+
+    /// The root of the workspace containing the crate invoking the macro.
+    Workspace,
+
+    /// The output directory of the build script of the crate invoking the
+    /// macro, for generated files.
+    OutDir,
+}
+
+impl Base {
+    pub fn from_name(name: &str) -> Option<Base> {
+        match name {
+            "manifest" => Some(Base::Manifest),
+            "workspace" => Some(Base::Workspace),
+            "out_dir" => Some(Base::OutDir),
+            _ => None,
+        }
+    }
+
+    /// Returns the directory that paths are relative to.
+    pub fn dir(self) -> Result<PathBuf, String> {
+        match self {
+            Base::Manifest => Ok(manifest_dir()),
+            Base::Workspace => workspace_root(&manifest_dir()),
+            Base::OutDir => out_dir(),
+        }
+    }
+}
+
+/// Returns the root of the crate currently being compiled.
+///
+/// This is used instead of the current directory, which isn't always the
+/// crate root - for example in workspaces, or under rust-analyzer.
+pub fn manifest_dir() -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .expect("CARGO_MANIFEST_DIR is not set")
+}
+
+/// Returns the output directory of the build script of the crate currently
+/// being compiled.
+pub fn out_dir() -> Result<PathBuf, String> {
+    std::env::var_os("OUT_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| "OUT_DIR is not set. Does this crate have a build script?".to_owned())
+}
+
+/// The name of the asset manifest file written to `OUT_DIR` by
+/// `resource::build`.
+pub const MANIFEST_FILE_NAME: &str = "resource_manifest.json";
+
+/// A file found in one of the asset directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asset {
+    /// The path of the file relative to the crate root, separated by `/`.
+    pub path: String,
+
+    /// The size of the file in bytes.
+    pub size: u64,
+
+    /// The 64-bit FNV-1a hash of the contents of the file.
+    pub hash: u64,
+}
+
+/// The asset manifest written to `OUT_DIR` by `resource::build`.
+pub struct BuildManifest {
+    strict: bool,
+    dirs: Vec<String>,
+    files: HashSet<String>,
+}
+
+impl BuildManifest {
+    /// Reads the asset manifest of the crate currently being compiled, if its
+    /// build script wrote one.
+    pub fn read() -> Result<Option<Arc<BuildManifest>>, String> {
+        // Cache the manifest, since it's checked by every macro invocation
+        static MANIFESTS: Mutex<Vec<(PathBuf, SystemTime, Arc<BuildManifest>)>> =
+            Mutex::new(vec![]);
+
+        let Ok(out_dir) = out_dir() else {
+            return Ok(None);
+        };
+        let path = out_dir.join(MANIFEST_FILE_NAME);
+        let Ok(modified) = std::fs::metadata(&path).and_then(|metadata| metadata.modified()) else {
+            return Ok(None);
+        };
+
+        let mut manifests = MANIFESTS.lock().unwrap_or_else(|e| e.into_inner());
+        let cached = manifests.iter().find(|(cached_path, cached_modified, _)| {
+            *cached_path == path && *cached_modified == modified
+        });
+        if let Some((_, _, manifest)) = cached {
+            return Ok(Some(manifest.clone()));
+        }
+
+        let manifest = Arc::new(Self::parse(&path)?);
+        manifests.retain(|(cached_path, _, _)| *cached_path != path);
+        manifests.push((path, modified, manifest.clone()));
+        Ok(Some(manifest))
+    }
+
+    /// Serializes a manifest of the given directories and the assets found in
+    /// them, in the format `read` expects.
+    pub fn to_json(strict: bool, dirs: &[String], assets: &[Asset]) -> String {
+        let files: Vec<_> = assets
+            .iter()
+            .map(|asset| {
+                serde_json::json!({
+                    "path": asset.path,
+                    "size": asset.size,
+                    "hash": format!("{:016x}", asset.hash),
+                })
+            })
+            .collect();
+        let json = serde_json::json!({
+            "strict": strict,
+            "dirs": dirs,
+            "files": files,
+        });
+        format!("{:#}\n", json)
+    }
+
+    fn parse(path: &Path) -> Result<BuildManifest, String> {
+        let error = |e: &dyn std::fmt::Display| {
+            format!("Failed to read asset manifest `{}`: {}", path.display(), e)
+        };
+        let json = std::fs::read(path).map_err(|e| error(&e))?;
+        Self::from_json(&json).map_err(|e| error(&e))
+    }
+
+    fn from_json(json: &[u8]) -> Result<BuildManifest, serde_json::Error> {
+        let json: serde_json::Value = serde_json::from_slice(json)?;
+
+        let strings = |value: &serde_json::Value| -> Vec<String> {
+            value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|value| value.as_str().map(str::to_owned))
+                .collect()
+        };
+
+        Ok(BuildManifest {
+            strict: json["strict"].as_bool().unwrap_or(false),
+            dirs: strings(&json["dirs"]),
+            files: json["files"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|file| file["path"].as_str().map(str::to_owned))
+                .collect(),
+        })
+    }
+
+    fn in_declared_dir(&self, path: &str) -> bool {
+        self.dirs.iter().any(|dir| {
+            path == dir
+                || path
+                    .strip_prefix(dir.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    /// Checks that a file, relative to the crate root, is a declared asset.
+    pub fn check_file(&self, path: &str) -> Result<(), String> {
+        let path = normalize(path);
+        if self.files.contains(&path) {
+            Ok(())
+        } else if self.in_declared_dir(&path) {
+            Err(format!(
+                "`{}` is not in the asset manifest written by the build script",
+                path
+            ))
+        } else if self.strict {
+            Err(format!(
+                "`{}` is not in any of the asset directories declared by the build script",
+                path
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks that a directory, relative to the crate root, is tracked by the
+    /// build script, so that adding or removing files triggers a rebuild.
+    pub fn check_dir(&self, path: &str) -> Result<(), String> {
+        let path = normalize(path);
+        if !self.strict || self.in_declared_dir(&path) {
+            Ok(())
+        } else {
+            Err(format!(
+                "`{}` is not in any of the asset directories declared by the build script, \
+                 so changes to it won't trigger a rebuild",
+                path
+            ))
+        }
+    }
+}
+
+/// Normalizes a relative path to the form used in the asset manifest.
+fn normalize(path: &str) -> String {
+    let mut components: Vec<String> = vec![];
+    for component in Path::new(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if components.last().is_some_and(|last| last != "..") => {
+                components.pop();
+            }
+            component => components.push(component.as_os_str().to_string_lossy().into_owned()),
+        }
+    }
+    components.join("/")
+}
+
+/// Returns the root of the workspace containing the given crate, or the
+/// crate root itself if it isn't part of a workspace.
+///
+/// Like Cargo, this looks for the nearest `Cargo.toml` with a `[workspace]`
+/// table. If a manifest can't be parsed, or the crate names its workspace
+/// explicitly, the root is taken from `cargo metadata` instead.
+pub fn workspace_root(manifest_dir: &Path) -> Result<PathBuf, String> {
+    for dir in manifest_dir.ancestors() {
+        let Ok(manifest) = std::fs::read_to_string(dir.join("Cargo.toml")) else {
+            continue;
+        };
+        let Ok(manifest) = manifest.parse::<toml::Table>() else {
+            return Ok(cargo_metadata(manifest_dir)?.workspace_root.clone());
+        };
+
+        if manifest.contains_key("workspace") {
+            return Ok(dir.to_owned());
+        }
+        if dir == manifest_dir
+            && manifest
+                .get("package")
+                .is_some_and(|package| package.get("workspace").is_some())
+        {
+            return Ok(cargo_metadata(manifest_dir)?.workspace_root.clone());
+        }
+    }
+
+    Ok(manifest_dir.to_owned())
+}
+
+/// Returns the root of the package with the given name, which must be in the
+/// same workspace as the given crate.
+///
+/// Dashes and underscores in package names are treated as equivalent, so
+/// that the name can be written as it would be in a path.
+///
+/// The package is found from the `members` of the workspace, so `cargo
+/// metadata` is only run for layouts which can't be resolved that way.
+pub fn package_dir(manifest_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let name = name.replace('-', "_");
+    if let Some(dir) = find_member(&workspace_root(manifest_dir)?, &name) {
+        return Ok(dir);
+    }
+
+    cargo_metadata(manifest_dir)?
+        .packages
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| format!("No package named `{}` in this workspace", name))
+}
+
+fn read_manifest(dir: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()
+}
+
+fn package_name(manifest: &toml::Table) -> Option<String> {
+    let name = manifest.get("package")?.get("name")?.as_str()?;
+    Some(name.replace('-', "_"))
+}
+
+/// Finds a package among the members of the workspace at `root`, or the root
+/// package itself.
+fn find_member(root: &Path, name: &str) -> Option<PathBuf> {
+    let manifest = read_manifest(root)?;
+    if package_name(&manifest).as_deref() == Some(name) {
+        return Some(root.to_owned());
+    }
+
+    let workspace = manifest.get("workspace")?;
+    let strings = |key| {
+        workspace
+            .get(key)
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str)
+    };
+
+    let excluded: Vec<PathBuf> = strings("exclude").map(|dir| root.join(dir)).collect();
+    strings("members")
+        .flat_map(|member| expand_member(root, member))
+        .filter(|dir| !excluded.contains(dir))
+        .find(|dir| {
+            read_manifest(dir)
+                .and_then(|manifest| package_name(&manifest))
+                .as_deref()
+                == Some(name)
+        })
+}
+
+/// Expands a workspace member, which may contain `*` and `?` wildcards.
+fn expand_member(root: &Path, member: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_owned()];
+    for component in member.split('/') {
+        if !component.contains(['*', '?']) {
+            for dir in &mut dirs {
+                dir.push(component);
+            }
+            continue;
+        }
+
+        dirs = dirs
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry.path().is_dir()
+                    && wildcard_match(component.as_bytes(), entry.file_name().as_encoded_bytes())
+            })
+            .map(|entry| entry.path())
+            .collect();
+    }
+    dirs
+}
+
+fn wildcard_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (Some((b'*', rest)), _) => {
+            wildcard_match(rest, name) || (!name.is_empty() && wildcard_match(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name_rest))) => wildcard_match(rest, name_rest),
+        (Some((p, rest)), Some((n, name_rest))) => p == n && wildcard_match(rest, name_rest),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// The parts of the output of `cargo metadata` used to resolve paths.
+struct Metadata {
+    workspace_root: PathBuf,
+    packages: HashMap<String, PathBuf>,
+}
+
+fn cargo_metadata(manifest_dir: &Path) -> Result<Arc<Metadata>, String> {
+    // Cache the metadata for each crate, since running `cargo metadata` is
+    // slow, and a proc macro server can expand macros for many crates.
+    static METADATA: Mutex<Vec<(PathBuf, Arc<Metadata>)>> = Mutex::new(vec![]);
+
+    let mut cached = METADATA.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, metadata)) = cached.iter().find(|(dir, _)| dir == manifest_dir) {
+        return Ok(metadata.clone());
+    }
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--offline",
+        ])
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"))
+        .output()
+        .map_err(|e| format!("Failed to run `cargo metadata`: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`cargo metadata` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse `cargo metadata` output: {}", e))?;
+
+    let packages = json["packages"].as_array().into_iter().flatten();
+    let metadata = Arc::new(Metadata {
+        workspace_root: json["workspace_root"]
+            .as_str()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| manifest_dir.to_owned()),
+        packages: packages
+            .filter_map(|package| {
+                let name = package["name"].as_str()?;
+                let manifest_path = Path::new(package["manifest_path"].as_str()?);
+                Some((name.replace('-', "_"), manifest_path.parent()?.to_owned()))
+            })
+            .collect(),
+    });
+    cached.push((manifest_dir.to_owned(), metadata.clone()));
+    Ok(metadata)
+}
+
+/// Returns `path` relative to `base`, going up with `..` as needed, or `path`
+/// itself if they have no common prefix.
+pub fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    let base: Vec<_> = base.components().collect();
+    let path_components: Vec<_> = path.components().collect();
+    let common = base
+        .iter()
+        .zip(&path_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return path.to_owned();
+    }
+
+    std::iter::repeat_n(Component::ParentDir, base.len() - common)
+        .chain(path_components[common..].iter().copied())
+        .collect()
+}
+
+/// A directory given to one of the list macros.
+pub struct ResolvedDir {
+    /// The absolute path of the directory, used to list its contents.
+    pub dir: PathBuf,
+
+    /// The path of the directory relative to the crate root, or to the build
+    /// script output directory for `Base::OutDir`. This is what the single
+    /// file macros expect.
+    pub relative: PathBuf,
+}
+
+pub fn resolve_dir(manifest_dir: &Path, base: Base, path: &str) -> Result<ResolvedDir, String> {
+    Ok(match base {
+        Base::Manifest => ResolvedDir {
+            dir: manifest_dir.join(path),
+            relative: PathBuf::from(path),
+        },
+        Base::Workspace => {
+            let root = workspace_root(manifest_dir)?;
+            let depth = manifest_dir
+                .strip_prefix(&root)
+                .map_err(|_| {
+                    format!(
+                        "The crate root `{}` is not inside the workspace root `{}`",
+                        manifest_dir.display(),
+                        root.display()
+                    )
+                })?
+                .components()
+                .count();
+
+            let mut relative: PathBuf = std::iter::repeat_n(Component::ParentDir, depth).collect();
+            relative.push(path);
+
+            ResolvedDir {
+                dir: root.join(path),
+                relative,
+            }
+        }
+        Base::OutDir => ResolvedDir {
+            dir: out_dir()?.join(path),
+            relative: PathBuf::from(path),
+        },
+    })
+}
+
+/// Options controlling how the list macros walk a directory.
+#[derive(Clone, Copy, Default)]
+pub struct ListOptions {
+    /// Whether to list the files in subdirectories too.
+    pub recursive: bool,
+
+    /// Whether to follow symlinks, rather than skipping them.
+    pub follow_symlinks: bool,
+}
+
+/// Lists the non-hidden files in a directory, returning pairs of names and
+/// paths relative to the crate root, sorted by name.
+///
+/// When listing recursively, the names of files in subdirectories are paths
+/// relative to the listed directory, separated by `/`.
+pub fn enumerate_files_paths(
+    dir: &ResolvedDir,
+    options: ListOptions,
+) -> Result<Vec<(String, String)>, String> {
+    let mut files_paths = vec![];
+
+    let canonical = canonicalize(&dir.dir)?;
+    let mut ancestors = vec![canonical];
+    visit_dir(
+        &dir.dir,
+        &dir.relative,
+        "",
+        options,
+        &mut ancestors,
+        &mut files_paths,
+    )?;
+
+    files_paths.sort();
+
+    Ok(files_paths)
+}
+
+fn visit_dir(
+    dir: &Path,
+    relative: &Path,
+    name_prefix: &str,
+    options: ListOptions,
+    ancestors: &mut Vec<PathBuf>,
+    files_paths: &mut Vec<(String, String)>,
+) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory `{}`: {}", dir.display(), e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let file_name = entry.file_name();
+        if file_name.as_encoded_bytes().starts_with(b".") {
+            continue;
+        }
+
+        let mut file_type = entry.file_type().map_err(|e| {
+            format!(
+                "Failed to read file type of `{}`: {}",
+                entry.path().display(),
+                e
+            )
+        })?;
+        if file_type.is_symlink() {
+            if !options.follow_symlinks {
+                continue;
+            }
+            file_type = std::fs::metadata(entry.path())
+                .map_err(|e| {
+                    format!(
+                        "Failed to follow symlink `{}`: {}",
+                        entry.path().display(),
+                        e
+                    )
+                })?
+                .file_type();
+        }
+
+        let is_dir = file_type.is_dir() && options.recursive;
+        if !file_type.is_file() && !is_dir {
+            continue;
+        }
+
+        let file_name = file_name.to_str().ok_or_else(|| {
+            format!(
+                "File name of `{}` is not valid UTF-8",
+                entry.path().display()
+            )
+        })?;
+        let name = format!("{}{}", name_prefix, file_name);
+        let path = relative.join(file_name);
+
+        if is_dir {
+            let canonical = canonicalize(&entry.path())?;
+            if ancestors.contains(&canonical) {
+                return Err(format!(
+                    "Symlink cycle detected at `{}`",
+                    entry.path().display()
+                ));
+            }
+
+            ancestors.push(canonical);
+            visit_dir(
+                &entry.path(),
+                &path,
+                &format!("{}/", name),
+                options,
+                ancestors,
+                files_paths,
+            )?;
+            ancestors.pop();
+        } else {
+            files_paths.push((name, path.to_string_lossy().into_owned()));
+        }
+    }
+
+    Ok(())
+}
+
+fn canonicalize(dir: &Path) -> Result<PathBuf, String> {
+    dir.canonicalize()
+        .map_err(|e| format!("Failed to read directory `{}`: {}", dir.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(strict: bool) -> BuildManifest {
+        BuildManifest {
+            strict,
+            dirs: vec!["assets".to_owned(), "shaders/compiled".to_owned()],
+            files: ["assets/a.txt", "assets/sub/b.txt", "shaders/compiled/c.spv"]
+                .into_iter()
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let asset = |path: &str| Asset {
+            path: path.to_owned(),
+            size: 1,
+            hash: 2,
+        };
+        let json = BuildManifest::to_json(
+            true,
+            &["assets".to_owned()],
+            &[asset("assets/a.txt"), asset("assets/sub/\"b\".txt")],
+        );
+        let manifest = BuildManifest::from_json(json.as_bytes()).unwrap();
+
+        assert!(manifest.check_file("assets/a.txt").is_ok());
+        assert!(manifest.check_file("assets/sub/\"b\".txt").is_ok());
+        assert!(manifest.check_file("assets/c.txt").is_err());
+        assert!(manifest.check_file("other/a.txt").is_err());
+        assert!(manifest.check_dir("assets/sub").is_ok());
+    }
+
+    #[test]
+    fn check_file_accepts_listed_files() {
+        for strict in [false, true] {
+            let manifest = manifest(strict);
+            assert!(manifest.check_file("assets/a.txt").is_ok());
+            assert!(manifest.check_file("./assets/sub/../sub/b.txt").is_ok());
+            assert!(manifest.check_file("shaders/compiled/c.spv").is_ok());
+        }
+    }
+
+    #[test]
+    fn check_file_rejects_unlisted_files_in_declared_dirs() {
+        for strict in [false, true] {
+            let error = manifest(strict).check_file("assets/new.txt").unwrap_err();
+            assert!(error.contains("not in the asset manifest"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn check_file_outside_declared_dirs_depends_on_strict() {
+        assert!(manifest(false).check_file("README.md").is_ok());
+        assert!(manifest(false).check_file("assets_old/a.txt").is_ok());
+
+        let error = manifest(true).check_file("README.md").unwrap_err();
+        assert!(
+            error.contains("not in any of the asset directories"),
+            "{}",
+            error
+        );
+        assert!(manifest(true).check_file("assets_old/a.txt").is_err());
+    }
+
+    #[test]
+    fn check_dir_outside_declared_dirs_depends_on_strict() {
+        for strict in [false, true] {
+            let manifest = manifest(strict);
+            assert!(manifest.check_dir("assets").is_ok());
+            assert!(manifest.check_dir("assets/sub").is_ok());
+            assert!(manifest.check_dir("shaders/compiled").is_ok());
+        }
+
+        assert!(manifest(false).check_dir("shaders").is_ok());
+        assert!(manifest(true).check_dir("shaders").is_err());
+        assert!(manifest(true).check_dir("tests").is_err());
+    }
+
+    #[test]
+    fn normalize_matches_manifest_paths() {
+        assert_eq!(normalize("./assets//a.txt"), "assets/a.txt");
+        assert_eq!(normalize("assets/sub/../a.txt"), "assets/a.txt");
+        assert_eq!(normalize("../other/a.txt"), "../other/a.txt");
+    }
+
+    #[test]
+    fn relative_path_goes_up_to_common_prefix() {
+        let base = Path::new("/ws/game");
+
+        assert_eq!(
+            relative_path(base, Path::new("/ws/game/assets/a.txt")),
+            Path::new("assets/a.txt")
+        );
+        assert_eq!(
+            relative_path(base, Path::new("/ws/engine/fonts/ui.ttf")),
+            Path::new("../engine/fonts/ui.ttf")
+        );
+        assert_eq!(
+            relative_path(Path::new("ws/game"), Path::new("other/a.txt")),
+            Path::new("other/a.txt")
+        );
+    }
+
+    fn repo_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn package_dir_finds_workspace_members() {
+        let root = repo_root();
+        let manifest_dir = root.join("cargo-resource");
+
+        assert_eq!(
+            package_dir(&manifest_dir, "resource-list-proc-macro").unwrap(),
+            root.join("resource_list_proc_macro")
+        );
+        assert_eq!(package_dir(&manifest_dir, "resource").unwrap(), root);
+    }
+
+    #[test]
+    fn package_dir_expands_member_wildcards() {
+        let root = std::env::temp_dir().join(format!("resource_members_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for (dir, manifest) in [
+            (
+                "",
+                "[ workspace ]  # Comments are allowed\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+            ),
+            ("crates/app", "[package]\nname = \"app\"\n"),
+            ("crates/game-assets", "[package]\nname = \"game-assets\"\n"),
+            ("crates/old", "[package]\nname = \"old\"\n"),
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("Cargo.toml"), manifest).unwrap();
+        }
+
+        // These packages have no targets, so `cargo metadata` would fail
+        let app = root.join("crates/app");
+        let workspace = workspace_root(&app);
+        let assets = package_dir(&app, "game_assets");
+        let old = find_member(&root, "old");
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(workspace.unwrap(), root);
+        assert_eq!(assets.unwrap(), root.join("crates/game-assets"));
+        assert_eq!(old, None);
+    }
+
+    #[test]
+    fn resolve_dir_relative_to_workspace() {
+        let root = repo_root();
+        let manifest_dir = root.join("cargo-resource");
+
+        assert_eq!(workspace_root(&manifest_dir).unwrap(), root);
+
+        let dir = resolve_dir(
+            &manifest_dir,
+            Base::Workspace,
+            "tests/resource_list_test_files",
+        )
+        .unwrap();
+        assert_eq!(dir.dir, root.join("tests/resource_list_test_files"));
+        assert_eq!(
+            dir.relative,
+            Path::new("..").join("tests/resource_list_test_files")
+        );
+
+        let files = enumerate_files_paths(&dir, ListOptions::default()).unwrap();
+        assert_eq!(files[0].0, "file_a.txt");
+        assert_eq!(
+            manifest_dir.join(&files[0].1).canonicalize().unwrap(),
+            root.join("tests/resource_list_test_files/file_a.txt")
+        );
+    }
+
+    #[test]
+    fn wildcards_match_names() {
+        assert!(wildcard_match(b"*", b"assets"));
+        assert!(wildcard_match(b"game-*", b"game-assets"));
+        assert!(wildcard_match(b"*-assets", b"game-assets"));
+        assert!(wildcard_match(b"as?ets", b"assets"));
+        assert!(!wildcard_match(b"game-*", b"assets"));
+        assert!(!wildcard_match(b"as?ets", b"asets"));
+    }
+}
@@ -2,70 +2,219 @@ use std::path::{Path, PathBuf};
 
 use proc_macro::TokenStream;
 use quote::quote;
+use resource_common::{
+    args::{Args, FileArg},
+    paths::{self, Base, BuildManifest, ListOptions},
+};
+use syn::{
+    Macro, Token,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+};
 
-fn read_path_argument(path: TokenStream) -> PathBuf {
-    let path = path.to_string();
-    let path = path.trim_start_matches('"');
-    let path = path.trim_end_matches('"');
-    PathBuf::from(path)
+mod manifest;
+
+fn expand_list(mut args: Args, str_list: bool) -> syn::Result<proc_macro2::TokenStream> {
+    let krate = args.take_crate_path()?;
+    let base = args.take_base()?;
+    let options = ListOptions {
+        recursive: args.take_flag("recursive")?,
+        follow_symlinks: args.take_flag("follow_symlinks")?,
+    };
+    let fallible = args.take_flag("try")?;
+    let path = args.finish()?;
+
+    let error = |e| syn::Error::new(path.span, e);
+    let dir = paths::resolve_dir(&paths::manifest_dir(), base, &path.value).map_err(error)?;
+    if base != Base::OutDir
+        && let Some(manifest) = BuildManifest::read().map_err(error)?
+    {
+        manifest
+            .check_dir(&dir.relative.to_string_lossy())
+            .map_err(error)?;
+    }
+    let (files, paths): (Vec<_>, Vec<_>) = paths::enumerate_files_paths(&dir, options)
+        .map_err(error)?
+        .into_iter()
+        .unzip();
+
+    let prefix = match base {
+        Base::OutDir => quote!(out_dir:),
+        _ => quote!(),
+    };
+
+    let single_file_macro = if str_list {
+        quote!(resource_str)
+    } else {
+        quote!(resource)
+    };
+    let single_file_macro = if fallible {
+        quote!(try #single_file_macro)
+    } else {
+        single_file_macro
+    };
+
+    // Expanded differently in each mode, so that files can be loaded in
+    // parallel in debug mode
+    Ok(quote! {
+        #krate::_resource_list!(#single_file_macro, [
+            #((#files, #prefix #paths)),*
+        ])
+    })
+}
+
+#[proc_macro]
+pub fn resource_list(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as Args);
+    expand_list(args, false)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn resource_str_list(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as Args);
+    expand_list(args, true)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
-fn enumerate_files_paths(path: &Path) -> (Vec<String>, Vec<String>) {
-    let mut files_paths = vec![];
-
-    let entries = std::fs::read_dir(path)
-        .unwrap_or_else(|e| panic!("Failed to read directory `{}`: {}", path.display(), e));
-
-    for entry in entries {
-        let entry = entry.expect("Failed to read directory entry");
-        let file_type = entry.file_type().unwrap_or_else(|e| {
-            panic!(
-                "Failed to read file type of `{}`: {}",
-                entry.path().display(),
-                e
-            )
-        });
-        if file_type.is_file() {
-            let file_name = entry.file_name();
-            let mut path = path.to_owned();
-            path.push(&file_name);
-
-            let file_name = file_name.to_string_lossy().into_owned();
-            if !file_name.starts_with('.') {
-                files_paths.push((file_name, path.to_string_lossy().into_owned()));
-            }
-        }
+/// Resolves the file named by a single file macro, checking that it exists.
+fn resolve_file(file: &FileArg) -> syn::Result<PathBuf> {
+    let FileArg {
+        base,
+        package,
+        path,
+    } = file;
+    let error = |e| syn::Error::new(path.span, e);
+
+    let root = match package {
+        Some(package) => paths::package_dir(&paths::manifest_dir(), &package.to_string())
+            .map_err(|e| syn::Error::new(package.span(), e))?,
+        None => base.dir().map_err(error)?,
+    };
+
+    let full_path = root.join(&path.value);
+    match std::fs::metadata(&full_path) {
+        Ok(metadata) if metadata.is_dir() => Err("Is a directory".to_owned()),
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.to_string()),
     }
+    .map_err(|e| error(format!("couldn't read `{}`: {}", full_path.display(), e)))?;
 
-    files_paths.sort();
+    Ok(full_path)
+}
 
-    let (files, paths) = files_paths.into_iter().unzip();
+fn expand_checked_path(
+    file: FileArg,
+    callback: Option<Macro>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let full_path = resolve_file(&file)?;
+    let FileArg {
+        base,
+        package,
+        path,
+    } = file;
+    let error = |e| syn::Error::new(path.span, e);
 
-    (files, paths)
+    if package.is_none()
+        && base == Base::Manifest
+        && let Some(manifest) = BuildManifest::read().map_err(error)?
+    {
+        manifest.check_file(&path.value).map_err(error)?;
+    }
+
+    let to_str = |path: &Path| {
+        path.to_str()
+            .map(str::to_owned)
+            .ok_or_else(|| error(format!("Path `{}` is not valid UTF-8", path.display())))
+    };
+
+    let checked_path = if package.is_some() {
+        // There's no environment variable for the other crate's directory, so
+        // use the path resolved above
+        let full_path = to_str(&full_path)?;
+        quote!(#full_path)
+    } else {
+        let var = match base {
+            Base::OutDir => "OUT_DIR",
+            _ => "CARGO_MANIFEST_DIR",
+        };
+        let filename = &path.value;
+        quote!(concat!(env!(#var), "/", #filename))
+    };
+
+    let Some(Macro {
+        path: callback,
+        tokens,
+        ..
+    }) = callback
+    else {
+        return Ok(checked_path);
+    };
+
+    // Embedded resources only keep the path relative to the crate root, so
+    // that paths on the build machine don't end up in the binary
+    let relative = to_str(&paths::relative_path(&paths::manifest_dir(), &full_path))?;
+    Ok(quote!(#callback!(#tokens, #relative, #checked_path)))
+}
+
+/// The argument to `_checked_path!`: a file, optionally followed by
+/// `=> callback!(args)` to pass the path relative to the crate root and the
+/// checked path to another macro as its last arguments, so that a macro which
+/// needs the path more than once only resolves it once.
+struct CheckedPathArgs {
+    file: FileArg,
+    callback: Option<Macro>,
 }
 
+impl Parse for CheckedPathArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let file = input.parse()?;
+        let callback = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(CheckedPathArgs { file, callback })
+    }
+}
+
+#[doc(hidden)]
 #[proc_macro]
-pub fn resource_list(path: TokenStream) -> TokenStream {
-    let path = read_path_argument(path);
-    let (files, paths) = enumerate_files_paths(&path);
-
-    (quote! {
-        [
-            #((#files, resource!(#paths)),)*
-        ]
-    })
-    .into()
+pub fn _checked_path(input: TokenStream) -> TokenStream {
+    let CheckedPathArgs { file, callback } = parse_macro_input!(input as CheckedPathArgs);
+    expand_checked_path(file, callback)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
+fn expand_manifest_entry(file: FileArg) -> syn::Result<proc_macro2::TokenStream> {
+    let full_path = resolve_file(&file)?;
+    let entry =
+        manifest::Entry::new(&file, &full_path).map_err(|e| syn::Error::new(file.path.span, e))?;
+    entry
+        .export()
+        .map_err(|e| syn::Error::new(file.path.span, e))?;
+
+    let manifest::Entry {
+        package,
+        path,
+        size,
+        hash,
+        file,
+        line,
+        column,
+    } = entry;
+    Ok(quote!((#package, #path, #size, #hash, #file, #line, #column)))
+}
+
+#[doc(hidden)]
 #[proc_macro]
-pub fn resource_str_list(path: TokenStream) -> TokenStream {
-    let path = read_path_argument(path);
-    let (files, paths) = enumerate_files_paths(&path);
-
-    (quote! {
-        [
-            #((#files, resource_str!(#paths)),)*
-        ]
-    })
-    .into()
+pub fn _manifest_entry(input: TokenStream) -> TokenStream {
+    let file = parse_macro_input!(input as FileArg);
+    expand_manifest_entry(file)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+use resource_common::{args::FileArg, fnv1a, paths::Base};
+
+/// If set while compiling, each embedded file is also recorded as a JSON file
+/// in this directory.
+const EXPORT_DIR_VAR: &str = "RESOURCE_MANIFEST_DIR";
+
+/// A file embedded by one of the single file macros, and where it was
+/// embedded.
+pub struct Entry {
+    pub package: String,
+    pub path: String,
+    pub size: u64,
+    pub hash: u64,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Entry {
+    pub fn new(file: &FileArg, full_path: &Path) -> Result<Self, String> {
+        let data = std::fs::read(full_path)
+            .map_err(|e| format!("couldn't read `{}`: {}", full_path.display(), e))?;
+
+        let package = match &file.package {
+            Some(package) => package.to_string(),
+            None => current_package(),
+        };
+        let path = match file.base {
+            Base::OutDir => format!("$OUT_DIR/{}", file.path.value),
+            _ => file.path.value.clone(),
+        };
+
+        let call_site = file.path.span.unwrap();
+        Ok(Entry {
+            package,
+            path,
+            size: data.len() as u64,
+            hash: fnv1a(&data),
+            file: call_site.file(),
+            line: call_site.line() as u32,
+            column: call_site.column() as u32,
+        })
+    }
+
+    /// Writes the entry to the directory named by `RESOURCE_MANIFEST_DIR`, if
+    /// it is set.
+    ///
+    /// Each file at each call site gets its own JSON file, so recompiling a
+    /// crate overwrites its entries rather than duplicating them.
+    pub fn export(&self) -> Result<(), String> {
+        let Some(dir) = std::env::var_os(EXPORT_DIR_VAR).map(PathBuf::from) else {
+            return Ok(());
+        };
+
+        // The list macros embed several files from one call site
+        let call_site = format!("{}:{}:{}:{}", self.file, self.line, self.column, self.path);
+        let file_name = format!(
+            "{}-{:016x}.json",
+            current_package(),
+            fnv1a(call_site.as_bytes())
+        );
+
+        let json = serde_json::json!({
+            "package": self.package,
+            "path": self.path,
+            "size": self.size,
+            "hash": format!("{:016x}", self.hash),
+            "file": self.file,
+            "line": self.line,
+            "column": self.column,
+        });
+
+        let error = |e: std::io::Error| {
+            format!(
+                "Failed to write asset manifest to `{}`: {}",
+                dir.display(),
+                e
+            )
+        };
+        std::fs::create_dir_all(&dir).map_err(error)?;
+        std::fs::write(dir.join(file_name), format!("{:#}\n", json)).map_err(error)
+    }
+}
+
+fn current_package() -> String {
+    std::env::var("CARGO_PKG_NAME").unwrap_or_default()
+}
@@ -0,0 +1,215 @@
+use std::{
+    borrow::{Borrow, ToOwned},
+    error::Error,
+    fmt, io,
+    ops::Deref,
+};
+
+use crate::ReadFromFile;
+
+/// Binary data with guaranteed alignment, which can be viewed as a slice of
+/// another type without copying.
+///
+/// Load it with the `align` option of the `resource!` macro. In release mode,
+/// the data is embedded with the requested alignment. In debug mode, it is
+/// loaded into a buffer aligned to `AlignedBytes::MAX_ALIGN`, which satisfies
+/// any alignment the macro accepts.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::resource;
+///
+/// let data = resource!("tests/aligned.bin", align = 16);
+/// let words: &[u32] = data.as_slice().unwrap();
+/// assert_eq!(words, [0x0101_0101; 4]);
+/// ```
+#[repr(transparent)]
+pub struct AlignedBytes([u8]);
+
+impl AlignedBytes {
+    /// The greatest alignment which can be requested with the `align` option.
+    pub const MAX_ALIGN: usize = 64;
+
+    #[doc(hidden)]
+    /// Please don't call this directly. It has to be public for the macro
+    /// but you shouldn't call it because it's not stable.
+    pub const fn _new(bytes: &[u8]) -> &AlignedBytes {
+        // Safe because `AlignedBytes` is a transparent wrapper around `[u8]`
+        unsafe { &*(bytes as *const [u8] as *const AlignedBytes) }
+    }
+
+    /// Returns the data as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Reinterprets the data as a slice of `T`, without copying.
+    ///
+    /// Fails if the data isn't aligned for `T`, if its length isn't a
+    /// multiple of the size of `T`, or if `T` is zero-sized.
+    pub fn as_slice<T: Pod>(&self) -> Result<&[T], CastError> {
+        let size = std::mem::size_of::<T>();
+        if size == 0 {
+            return Err(CastError::ZeroSized);
+        }
+        if self.0.as_ptr().align_offset(std::mem::align_of::<T>()) != 0 {
+            return Err(CastError::Misaligned);
+        }
+        if !self.0.len().is_multiple_of(size) {
+            return Err(CastError::LengthMismatch);
+        }
+
+        // Safe because the pointer is aligned, the length fits exactly, and
+        // `Pod` types are valid for any bit pattern
+        Ok(unsafe { std::slice::from_raw_parts(self.0.as_ptr().cast(), self.0.len() / size) })
+    }
+}
+
+impl Deref for AlignedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for AlignedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for AlignedBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for AlignedBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for AlignedBytes {}
+
+impl ToOwned for AlignedBytes {
+    type Owned = AlignedBuf;
+
+    fn to_owned(&self) -> AlignedBuf {
+        AlignedBuf::from(&self.0)
+    }
+}
+
+/// An owned buffer of `AlignedBytes`, aligned to `AlignedBytes::MAX_ALIGN`.
+#[derive(Clone)]
+pub struct AlignedBuf {
+    chunks: Vec<Chunk>,
+    len: usize,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, align(64))]
+struct Chunk([u8; AlignedBytes::MAX_ALIGN]);
+
+impl From<&[u8]> for AlignedBuf {
+    fn from(bytes: &[u8]) -> Self {
+        let mut chunks = vec![
+            Chunk([0; AlignedBytes::MAX_ALIGN]);
+            bytes.len().div_ceil(AlignedBytes::MAX_ALIGN)
+        ];
+        for (chunk, bytes) in chunks.iter_mut().zip(bytes.chunks(AlignedBytes::MAX_ALIGN)) {
+            chunk.0[..bytes.len()].copy_from_slice(bytes);
+        }
+
+        AlignedBuf {
+            chunks,
+            len: bytes.len(),
+        }
+    }
+}
+
+impl Deref for AlignedBuf {
+    type Target = AlignedBytes;
+
+    fn deref(&self) -> &AlignedBytes {
+        // Safe because the chunks are contiguous and hold at least `len` bytes
+        let bytes =
+            unsafe { std::slice::from_raw_parts(self.chunks.as_ptr().cast::<u8>(), self.len) };
+        AlignedBytes::_new(bytes)
+    }
+}
+
+impl Borrow<AlignedBytes> for AlignedBuf {
+    fn borrow(&self) -> &AlignedBytes {
+        self
+    }
+}
+
+impl AsRef<AlignedBytes> for AlignedBuf {
+    fn as_ref(&self) -> &AlignedBytes {
+        self
+    }
+}
+
+impl AsRef<[u8]> for AlignedBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl ReadFromFile for AlignedBuf {
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        Ok(AlignedBuf::from(bytes))
+    }
+}
+
+/// Types which can be viewed from any correctly sized and aligned bytes.
+///
+/// # Safety
+///
+/// The type must be `Copy`, have no padding, and be valid for any bit
+/// pattern. Structs must be `#[repr(C)]` or `#[repr(transparent)]`.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// The reason `AlignedBytes::as_slice` failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastError {
+    /// The data isn't aligned for the requested type.
+    Misaligned,
+
+    /// The length of the data isn't a multiple of the size of the requested
+    /// type.
+    LengthMismatch,
+
+    /// The requested type is zero-sized.
+    ZeroSized,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CastError::Misaligned => write!(f, "data is not aligned for the requested type"),
+            CastError::LengthMismatch => write!(
+                f,
+                "data length is not a multiple of the size of the requested type"
+            ),
+            CastError::ZeroSized => write!(f, "cannot view data as a zero-sized type"),
+        }
+    }
+}
+
+impl Error for CastError {}
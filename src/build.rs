@@ -0,0 +1,140 @@
+//! Helpers for build scripts.
+//!
+//! Use these from your `build.rs` to declare the directories your assets live
+//! in. Cargo will then rebuild your crate whenever a file in them changes -
+//! which `resource_list!` relies on to pick up added and removed files - and
+//! the macros will check every path against the list of declared assets.
+//!
+//! Requires the `build` feature. Enable it only for your build dependency, so
+//! that the dependencies it needs aren't built into your binary:
+//!
+//! ```toml
+//! [build-dependencies]
+//! resource = { version = "0.6", features = ["build"] }
+//! ```
+//!
+//! ```rust,no_run
+//! // In `main` in build.rs
+//! resource::build::AssetManifest::new()
+//!     .dir("assets")
+//!     .dir("shaders")
+//!     .write()
+//!     .unwrap();
+//! ```
+
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use resource_common::{
+    fnv1a,
+    paths::{self, BuildManifest, ListOptions, ResolvedDir},
+};
+
+pub use resource_common::paths::{Asset, MANIFEST_FILE_NAME};
+
+/// Scans asset directories and writes a manifest of their contents to
+/// `OUT_DIR`.
+#[derive(Debug, Clone, Default)]
+pub struct AssetManifest {
+    dirs: Vec<PathBuf>,
+    strict: bool,
+}
+
+impl AssetManifest {
+    /// Creates a manifest with no asset directories, which isn't strict.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a directory of assets, relative to the crate root. Its
+    /// subdirectories are included, but hidden files are skipped.
+    pub fn dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.dirs.push(dir.as_ref().to_owned());
+        self
+    }
+
+    /// If `true`, the macros will refuse to load any file which isn't in one
+    /// of the declared directories. Otherwise only files inside those
+    /// directories are checked.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Scans the declared directories, returning the assets found.
+    ///
+    /// Symlinks are followed, and directories are listed the same way as by
+    /// `resource_list!`, so a symlink cycle is an error.
+    pub fn scan(&self) -> io::Result<Vec<Asset>> {
+        let manifest_dir = env_path("CARGO_MANIFEST_DIR")?;
+        let options = ListOptions {
+            recursive: true,
+            follow_symlinks: true,
+        };
+
+        let mut assets = vec![];
+        for dir in &self.dirs {
+            let dir = ResolvedDir {
+                dir: manifest_dir.join(dir),
+                relative: dir.clone(),
+            };
+            for (_, path) in
+                paths::enumerate_files_paths(&dir, options).map_err(io::Error::other)?
+            {
+                let data = std::fs::read(manifest_dir.join(&path))?;
+                assets.push(Asset {
+                    path: slash_path(Path::new(&path)),
+                    size: data.len() as u64,
+                    hash: fnv1a(&data),
+                });
+            }
+        }
+
+        assets.sort_by(|a, b| a.path.cmp(&b.path));
+        assets.dedup_by(|a, b| a.path == b.path);
+        Ok(assets)
+    }
+
+    /// Scans the declared directories, tells Cargo to rerun the build script
+    /// when they change, and writes the manifest to `OUT_DIR`.
+    ///
+    /// Returns the assets found.
+    pub fn write(&self) -> io::Result<Vec<Asset>> {
+        for dir in &self.dirs {
+            println!("cargo:rerun-if-changed={}", dir.display());
+        }
+
+        let assets = self.scan()?;
+        let out_dir = env_path("OUT_DIR")?;
+        std::fs::write(out_dir.join(MANIFEST_FILE_NAME), self.to_json(&assets))?;
+
+        Ok(assets)
+    }
+
+    fn to_json(&self, assets: &[Asset]) -> String {
+        let dirs: Vec<_> = self.dirs.iter().map(|dir| slash_path(dir)).collect();
+        BuildManifest::to_json(self.strict, &dirs, assets)
+    }
+}
+
+fn env_path(var: &str) -> io::Result<PathBuf> {
+    std::env::var_os(var).map(PathBuf::from).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "{} is not set. Is this being called from a build script?",
+                var
+            ),
+        )
+    })
+}
+
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
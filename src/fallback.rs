@@ -0,0 +1,100 @@
+use std::{
+    any::{Any, TypeId},
+    borrow::ToOwned,
+    collections::BTreeMap,
+    path::Path,
+    sync::{PoisonError, RwLock},
+};
+
+static FALLBACKS: RwLock<BTreeMap<TypeId, Box<dyn Any + Send + Sync>>> =
+    RwLock::new(BTreeMap::new());
+
+/// What to do when the file for a dynamically loaded resource is missing.
+///
+/// Set with `set_fallback`.
+pub enum Fallback<T> {
+    /// Panic. This is the default.
+    Panic,
+
+    /// Use the value returned by this function instead. The real file is
+    /// picked up by `reload_if_changed` once it appears.
+    ///
+    /// The function is given the path of the missing file, so that it can
+    /// log a warning, or pick a value based on the path.
+    Value(fn(&Path) -> T),
+}
+
+impl<T: Placeholder> Fallback<T> {
+    /// Use the built-in placeholder for this type, without a warning.
+    pub fn placeholder() -> Self {
+        Fallback::Value(|_| T::placeholder())
+    }
+}
+
+/// A type with a built-in placeholder value, used by `Fallback::placeholder`.
+pub trait Placeholder {
+    fn placeholder() -> Self;
+}
+
+/// The placeholder for text is an empty string.
+impl Placeholder for String {
+    fn placeholder() -> String {
+        String::new()
+    }
+}
+
+/// The placeholder for binary data is a 16x16 magenta and black checkerboard
+/// PNG image.
+impl Placeholder for Vec<u8> {
+    fn placeholder() -> Vec<u8> {
+        include_bytes!("placeholder.png").to_vec()
+    }
+}
+
+/// Sets what happens when the file for a `Resource<B>` is missing when it is
+/// first loaded.
+///
+/// This only applies in debug mode, as in release mode a missing file is a
+/// compile error.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::{set_fallback, Fallback};
+///
+/// // Use a magenta checkerboard for missing images
+/// set_fallback::<[u8]>(Fallback::placeholder());
+///
+/// // Use a custom value for missing text, with a warning
+/// set_fallback::<str>(Fallback::Value(|path| {
+///     eprintln!("Missing `{}`", path.display());
+///     "MISSING".to_owned()
+/// }));
+/// ```
+pub fn set_fallback<B>(fallback: Fallback<B::Owned>)
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: 'static,
+{
+    let mut fallbacks = FALLBACKS.write().unwrap_or_else(PoisonError::into_inner);
+    let type_id = TypeId::of::<B::Owned>();
+    match fallback {
+        Fallback::Panic => fallbacks.remove(&type_id),
+        Fallback::Value(load_fn) => fallbacks.insert(type_id, Box::new(load_fn)),
+    };
+}
+
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+pub(crate) fn fallback<T: 'static>(path: &Path) -> Option<T> {
+    let load_fn = FALLBACKS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&TypeId::of::<T>())
+        .and_then(|load_fn| load_fn.downcast_ref::<fn(&Path) -> T>())
+        .copied();
+
+    load_fn.map(|load_fn| load_fn(path))
+}
@@ -0,0 +1,108 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::ReadFromFile;
+
+/// How long a file must go unmodified before `reload_if_changed` picks up the
+/// change. Editors often truncate a file before writing to it, so reading it
+/// straight away can see it empty or half-written.
+const DEBOUNCE_TIME: Duration = Duration::from_millis(100);
+
+/// How many times to try rereading a file before giving up. Editors which
+/// save by renaming a temporary file leave a brief window where the file
+/// doesn't exist.
+const READ_ATTEMPTS: u32 = 5;
+
+const READ_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+/// The data read from a file, along with its modification time and its size
+/// in bytes.
+pub(crate) type ReadResult<T> = io::Result<(T, Option<SystemTime>, usize)>;
+
+/// Rereads a file for a reload, retrying if it is missing or is modified
+/// during the read.
+///
+/// The first load uses `read_complete` instead, so that a file which really
+/// is missing fails straight away.
+pub(crate) fn reread_complete<T: ReadFromFile>(path: &Path) -> ReadResult<T> {
+    let mut attempt = 1;
+    loop {
+        match read_complete(path) {
+            Err(e) if attempt < READ_ATTEMPTS && is_transient(&e) => {
+                attempt += 1;
+                std::thread::sleep(READ_RETRY_DELAY);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Reads a file, failing with `Interrupted` if it is modified during the
+/// read. Returns the data along with its modification time, if the platform
+/// supports it, and the size of the file.
+pub(crate) fn read_complete<T: ReadFromFile>(path: &Path) -> ReadResult<T> {
+    let before = std::fs::metadata(path)?;
+    let data = T::read_from_file(path)?;
+    let after = std::fs::metadata(path)?;
+
+    let modified = after.modified().ok();
+    if before.len() != after.len() || before.modified().ok() != modified {
+        return Err(io::Error::new(
+            io::ErrorKind::Interrupted,
+            "file was modified while it was being read",
+        ));
+    }
+
+    Ok((data, modified, after.len() as usize))
+}
+
+fn is_transient(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::NotFound | io::ErrorKind::Interrupted
+    )
+}
+
+/// The file a resource was loaded from, and when it was loaded.
+#[derive(Clone)]
+pub(crate) struct FileState {
+    pub path: PathBuf,
+
+    /// The modification time of the file when it was loaded, or `None` if
+    /// the file was missing and a fallback was used instead.
+    pub modified: Option<SystemTime>,
+
+    pub loaded_at: SystemTime,
+
+    /// The number of times the file has been reloaded successfully.
+    pub generation: u64,
+}
+
+impl FileState {
+    fn current_modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    pub fn changed(&self) -> bool {
+        let modified = self.current_modified();
+        modified.is_some() && modified != self.modified
+    }
+
+    /// Returns `true` if the file has changed and has since gone unmodified
+    /// long enough to be read.
+    pub fn changed_and_settled(&self) -> bool {
+        match self.current_modified() {
+            // A modification time in the future can't be debounced, so treat
+            // it as settled rather than never reloading.
+            Some(modified) if Some(modified) != self.modified => modified
+                .elapsed()
+                .map_or(true, |elapsed| elapsed >= DEBOUNCE_TIME),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    borrow::ToOwned,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex, OnceLock, PoisonError, mpsc},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use crate::{
+    ReadFromFile, ReloadOutcome, Resource,
+    file::{ReadResult, reread_complete},
+};
+
+/// How many threads load files in the background.
+const LOADER_THREADS: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static LOADER: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+
+/// Runs a job on the loader threads, starting them if this is the first job.
+fn spawn_job(job: Job) {
+    let sender = LOADER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for i in 0..LOADER_THREADS {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("resource-loader-{}", i))
+                .spawn(move || {
+                    loop {
+                        let job = receiver
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .recv();
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => break,
+                        }
+                    }
+                })
+                .expect("Failed to start resource loader thread");
+        }
+        sender
+    });
+
+    sender
+        .send(job)
+        .expect("Resource loader threads have stopped");
+}
+
+/// A value being loaded in the background.
+///
+/// It works with any executor, as the file IO happens on a small pool of
+/// threads owned by this crate. In release mode, the value is embedded and
+/// the future resolves immediately.
+///
+/// Returned by the `resource_async!` macro.
+pub struct LoadFuture<T>(State<T>);
+
+enum State<T> {
+    Ready(Option<T>),
+    Loading(Arc<Mutex<Shared<T>>>),
+}
+
+struct Shared<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+// The value is never pinned
+impl<T> Unpin for LoadFuture<T> {}
+
+impl<T> LoadFuture<T> {
+    #[doc(hidden)]
+    /// Please don't call this directly. It has to be public for the macro
+    /// but you shouldn't call it because it's not stable.
+    pub fn _ready(value: T) -> Self {
+        LoadFuture(State::Ready(Some(value)))
+    }
+}
+
+impl<T: Send + 'static> LoadFuture<T> {
+    /// Runs `load_fn` on the loader threads. A panic in `load_fn` is resumed
+    /// when the future is polled.
+    pub(crate) fn spawn(load_fn: impl FnOnce() -> T + Send + 'static) -> Self {
+        let shared = Arc::new(Mutex::new(Shared {
+            result: None,
+            waker: None,
+        }));
+
+        let worker_shared = Arc::clone(&shared);
+        spawn_job(Box::new(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(load_fn));
+
+            let waker = {
+                let mut shared = worker_shared.lock().unwrap_or_else(PoisonError::into_inner);
+                shared.result = Some(result);
+                shared.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }));
+
+        LoadFuture(State::Loading(shared))
+    }
+}
+
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+impl<B> LoadFuture<Resource<B>>
+where
+    B: 'static + ToOwned + Sync + ?Sized,
+    B::Owned: ReadFromFile + Send + Sync + 'static,
+{
+    #[doc(hidden)]
+    /// Please don't call this directly. It has to be public for the macro
+    /// but you shouldn't call it because it's not stable.
+    pub fn _from_file(path: &'static str) -> Self {
+        LoadFuture::spawn(move || Resource::_from_file(path))
+    }
+}
+
+impl<T> Future for LoadFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+        match &mut self.get_mut().0 {
+            State::Ready(value) => {
+                Poll::Ready(value.take().expect("LoadFuture polled after completion"))
+            }
+            State::Loading(shared) => {
+                let mut shared = shared.lock().unwrap_or_else(PoisonError::into_inner);
+                match shared.result.take() {
+                    Some(Ok(value)) => Poll::Ready(value),
+                    Some(Err(payload)) => {
+                        drop(shared);
+                        panic::resume_unwind(payload)
+                    }
+                    None => {
+                        shared.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reloads a resource in the background.
+///
+/// Returned by `Resource::reload_async`.
+pub struct ReloadFuture<'a, B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    resource: &'a mut Resource<B>,
+    load: Option<LoadFuture<ReadResult<B::Owned>>>,
+}
+
+impl<B> Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: ReadFromFile + Send + 'static,
+{
+    /// Reloads the resource, reading the file on a background thread.
+    ///
+    /// Like `reload`, the previous data is kept if the file can't be read,
+    /// and resources created from static data resolve immediately to
+    /// `ReloadOutcome::Unchanged`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resource::resource_str;
+    ///
+    /// async fn reload_config() {
+    ///     let mut config = resource_str!("Cargo.toml");
+    ///     config.reload_async().await;
+    /// }
+    /// ```
+    pub fn reload_async(&mut self) -> ReloadFuture<'_, B> {
+        let load = self.data.file().map(|file| {
+            let path: PathBuf = file.path.clone();
+            LoadFuture::spawn(move || reread_complete(&path))
+        });
+
+        ReloadFuture {
+            resource: self,
+            load,
+        }
+    }
+}
+
+impl<B> Future for ReloadFuture<'_, B>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: ReadFromFile,
+{
+    type Output = ReloadOutcome;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<ReloadOutcome> {
+        let this = self.get_mut();
+        let Some(load) = &mut this.load else {
+            return Poll::Ready(ReloadOutcome::Unchanged);
+        };
+
+        match Pin::new(load).poll(cx) {
+            Poll::Ready(result) => {
+                this.load = None;
+                Poll::Ready(this.resource.apply_reload(result))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
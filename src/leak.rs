@@ -0,0 +1,94 @@
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+    time::SystemTime,
+};
+
+/// The default for `set_leak_limit`.
+const DEFAULT_LEAK_LIMIT: usize = 256 * 1024 * 1024;
+
+/// Leaked data, keyed by the file and modification time it was loaded from,
+/// so that leaking a file which hasn't changed reuses the previous leak.
+type Leaks = BTreeMap<(TypeId, PathBuf, SystemTime), &'static (dyn Any + Send + Sync)>;
+
+struct Registry {
+    leaks: Leaks,
+    bytes: usize,
+    limit: usize,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    leaks: BTreeMap::new(),
+    bytes: 0,
+    limit: DEFAULT_LEAK_LIMIT,
+});
+
+/// Returns the total size in bytes of the data leaked by `Resource::into_static`.
+pub fn leaked_bytes() -> usize {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .bytes
+}
+
+/// Sets how many bytes `Resource::into_static` may leak in total before it
+/// panics. The default is 256 MiB.
+///
+/// Leaking the same unchanged file again reuses the earlier leak, so the
+/// limit is only reached by leaking many distinct files, or by leaking a
+/// file each time it is reloaded.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::set_leak_limit;
+///
+/// set_leak_limit(1024 * 1024 * 1024);
+/// ```
+pub fn set_leak_limit(bytes: usize) {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .limit = bytes;
+}
+
+/// Leaks the data produced by `data`, or returns the data previously leaked
+/// for the same file, if it hasn't changed since.
+///
+/// Panics if the leak limit would be exceeded.
+pub(crate) fn leak<T>(
+    file: Option<(&Path, SystemTime)>,
+    size: usize,
+    data: impl FnOnce() -> T,
+) -> &'static T
+where
+    T: Any + Send + Sync,
+{
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let key = file.map(|(path, modified)| (TypeId::of::<T>(), path.to_owned(), modified));
+    if let Some(leaked) = key.as_ref().and_then(|key| registry.leaks.get(key))
+        && let Some(leaked) = leaked.downcast_ref()
+    {
+        return leaked;
+    }
+
+    let bytes = registry.bytes.saturating_add(size);
+    if bytes > registry.limit {
+        panic!(
+            "Leaking {} more bytes would exceed the leak limit of {} bytes. \
+             Avoid calling `into_static` on every reload, or raise the limit with \
+             `set_leak_limit`.",
+            size, registry.limit
+        );
+    }
+
+    let leaked: &'static T = Box::leak(Box::new(data()));
+    registry.bytes = bytes;
+    if let Some(key) = key {
+        registry.leaks.insert(key, leaked);
+    }
+    leaked
+}
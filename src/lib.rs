@@ -7,6 +7,18 @@
 //! You can change the default behaviour, in debug or release mode, by using the
 //! `force-static` and `force-dynamic` features.
 //!
+//! Enable the `serde` feature to serialize resources as their contents, or as
+//! their paths with `serde_path`.
+//!
+//! Enable the `bytes` feature to share resources as `bytes::Bytes` without
+//! copying them.
+//!
+//! Enable the `manifest` feature to list every embedded file at runtime with
+//! `manifest()`, to audit what ends up in your release binary.
+//!
+//! Enable the `build` feature in your `[build-dependencies]` to declare your
+//! asset directories from a build script with `build::AssetManifest`.
+//!
 //! ```rust,ignore
 //! use resource::{resource, resource_str};
 //!
@@ -25,830 +37,3568 @@
 //!     Texture::decode);
 //! ```
 
+// Lets the paths generated by the list macros resolve within this crate too
+extern crate self as resource;
+
 #[cfg(all(feature = "force-static", feature = "force-dynamic"))]
 compile_error!("resource: Cannot enable both the force-static and force-dynamic features.");
 
-pub use self::resource::Resource;
-
-use std::path::Path;
-
+pub use self::aligned::{AlignedBuf, AlignedBytes, CastError, Pod};
+pub use self::fallback::{Fallback, Placeholder, set_fallback};
+pub use self::future::{LoadFuture, ReloadFuture};
+pub use self::leak::{leaked_bytes, set_leak_limit};
+#[cfg(feature = "manifest")]
+pub use self::manifest::{ManifestEntry, manifest, manifest_json};
+pub use self::parallel::LoadErrors;
+pub use self::progress::LoadProgress;
+pub use self::reader::ResourceReader;
+pub use self::resource_impl::{LazyResource, LazyResourceGuard};
+#[doc(hidden)]
+pub use self::shared::Embedded as _Embedded;
+pub use self::transformed::Transformed;
+
+use std::{
+    borrow::{Borrow, Cow, ToOwned},
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    hash::{Hash, Hasher},
+    io,
+    ops::Deref,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use self::{
+    file::{FileState, ReadResult, read_complete, reread_complete},
+    shared::{Data, Embedded},
+};
+
+#[cfg(feature = "build")]
+pub mod build;
+#[cfg(feature = "serde")]
+pub mod serde_path;
+
+mod aligned;
+mod fallback;
+mod file;
+mod future;
+mod leak;
+#[cfg(feature = "manifest")]
+mod manifest;
+mod parallel;
+mod progress;
+mod reader;
+mod shared;
+mod transformed;
+
+/// Load every file in a directory as a binary resource.
+///
+/// Expands to an array of `(file_name, resource)` pairs, sorted by file name.
+/// The directory is relative to the root of your crate, and hidden files are
+/// skipped.
+///
+/// To list a directory relative to the root of your workspace instead, pass
+/// `relative_to = workspace`, or for files generated by your build script,
+/// `relative_to = out_dir`.
+///
+/// Other options:
+///
+/// - `recursive = true` also lists files in subdirectories, named by their
+///   path relative to the listed directory, such as `"fonts/ui.ttf"`.
+/// - `follow_symlinks = true` includes symlinked files, and directories when
+///   listing recursively, instead of skipping them. Symlink cycles are a
+///   compile error.
+/// - `try = true` expands to a `Result` instead, with a `LoadErrors` listing
+///   every file which couldn't be read rather than panicking. When statically
+///   including, it is always `Ok`.
+///
+/// File names which aren't valid UTF-8 are a compile error.
+///
+/// In debug mode, the files are read in parallel. If any can't be read, the
+/// panic lists every one of them rather than only the first.
+///
+/// The expansion refers to this crate as `::resource`. If you re-export it
+/// from another crate, pass the path to the re-export with the `crate` option.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::resource_list;
+///
+/// let files = resource_list!("tests/resource_list_test_files");
+/// assert_eq!(files[0].0, "file_a.txt");
+/// assert_eq!(&*files[0].1, b"A\n");
+///
+/// mod engine {
+///     pub use resource;
+/// }
+///
+/// let files = resource_list!("tests/resource_list_test_files", crate = engine::resource);
+/// assert_eq!(files.len(), 3);
+/// ```
 pub use resource_list_proc_macro::resource_list;
 
+/// Load every file in a directory as a text resource.
+///
+/// Expands to an array of `(file_name, resource)` pairs, sorted by file name.
+/// The directory is relative to the root of your crate, and hidden files are
+/// skipped.
+///
+/// To list a directory relative to the root of your workspace instead, pass
+/// `relative_to = workspace`, or for files generated by your build script,
+/// `relative_to = out_dir`.
+///
+/// Other options:
+///
+/// - `recursive = true` also lists files in subdirectories, named by their
+///   path relative to the listed directory, such as `"fonts/ui.ttf"`.
+/// - `follow_symlinks = true` includes symlinked files, and directories when
+///   listing recursively, instead of skipping them. Symlink cycles are a
+///   compile error.
+/// - `try = true` expands to a `Result` instead, with a `LoadErrors` listing
+///   every file which couldn't be read rather than panicking. When statically
+///   including, it is always `Ok`.
+///
+/// File names which aren't valid UTF-8 are a compile error.
+///
+/// In debug mode, the files are read in parallel. If any can't be read, the
+/// panic lists every one of them rather than only the first.
+///
+/// The expansion refers to this crate as `::resource`. If you re-export it
+/// from another crate, pass the path to the re-export with the `crate` option.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::resource_str_list;
+///
+/// let files = resource_str_list!("tests/resource_list_test_files");
+/// assert_eq!(files[0].0, "file_a.txt");
+/// assert_eq!(&*files[0].1, "A\n");
+/// ```
 pub use resource_list_proc_macro::resource_str_list;
 
-/// Used internally.
-///
-/// Only used by the dynamic versions of `Resource` to make it generic
-/// over both strings and bytes. Represents something that can be read
-/// straight from a file.
-pub trait ReadFromFile {
-    fn read_from_file(path: &Path) -> Self;
+#[doc(hidden)]
+pub use resource_list_proc_macro::_checked_path;
+
+#[cfg(feature = "manifest")]
+#[doc(hidden)]
+pub use inventory as _inventory;
+
+#[cfg(feature = "manifest")]
+#[doc(hidden)]
+pub use resource_list_proc_macro::_manifest_entry;
+
+/// Used internally by the list macros, to load the files in parallel in debug
+/// mode.
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _resource_list {
+    (resource_str, [ $(($name:literal, $($path:tt)+)),* ]) => {
+        $crate::Resource::<str>::_from_named_files([ $(($name, $crate::_checked_path!($($path)+))),* ])
+    };
+
+    (resource, [ $(($name:literal, $($path:tt)+)),* ]) => {
+        $crate::Resource::<[u8]>::_from_named_files([ $(($name, $crate::_checked_path!($($path)+))),* ])
+    };
+
+    (try resource_str, [ $(($name:literal, $($path:tt)+)),* ]) => {
+        $crate::Resource::<str>::_try_from_named_files([ $(($name, $crate::_checked_path!($($path)+))),* ])
+    };
+
+    (try resource, [ $(($name:literal, $($path:tt)+)),* ]) => {
+        $crate::Resource::<[u8]>::_try_from_named_files([ $(($name, $crate::_checked_path!($($path)+))),* ])
+    };
 }
 
-impl ReadFromFile for String {
-    fn read_from_file(path: &Path) -> String {
-        std::fs::read_to_string(path)
-            .map_err(|e| eprintln!("Failed to read `{}` as string: {}", path.display(), e))
-            .unwrap()
-    }
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _resource_list {
+    ($macro:ident, [ $(($name:literal, $($path:tt)+)),* ]) => {{
+        let files = [ $(($name, $crate::$macro!($($path)+))),* ];
+        $crate::LoadProgress::_report_embedded(files.iter().map(|(_, resource)| resource.len()));
+        files
+    }};
+
+    (try $macro:ident, [ $($files:tt)* ]) => {
+        ::std::result::Result::<_, $crate::LoadErrors>::Ok($crate::_resource_list!($macro, [ $($files)* ]))
+    };
 }
 
-impl ReadFromFile for Vec<u8> {
-    fn read_from_file(path: &Path) -> Vec<u8> {
-        std::fs::read(path)
-            .map_err(|e| eprintln!("Failed to read `{}` as bytes: {}", path.display(), e))
-            .unwrap()
-    }
+/// Used internally to check the `align` option of `resource!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _check_align {
+    ($align:literal) => {
+        const _: () = ::std::assert!(
+            ($align as usize).is_power_of_two()
+                && $align as usize <= $crate::AlignedBytes::MAX_ALIGN,
+            "`align` must be a power of two no greater than 64"
+        );
+    };
 }
 
+/// Used internally by the single file macros in release mode, to embed a file
+/// given its path relative to the crate root and the path checked by
+/// `_checked_path!`.
 #[cfg(any(
-    feature = "force-dynamic",
-    all(not(feature = "force-static"), debug_assertions)
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
 ))]
-mod resource {
-    use std::{
-        borrow::{Cow, ToOwned},
-        convert::AsRef,
-        ops::Deref,
-        path::{Path, PathBuf},
-        time::SystemTime,
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _embed {
+    (str, $path:literal, $($file:tt)+) => {{
+        static EMBEDDED: $crate::_Embedded<str> = $crate::_Embedded::_new(include_str!($($file)+), $path);
+        $crate::Resource::<str>::_from_embedded(&EMBEDDED)
+    }};
+
+    (bytes, $path:literal, $($file:tt)+) => {{
+        static EMBEDDED: $crate::_Embedded<[u8]> = $crate::_Embedded::_new(include_bytes!($($file)+), $path);
+        $crate::Resource::<[u8]>::_from_embedded(&EMBEDDED)
+    }};
+
+    (decode $ty:ty, $path:literal, $($file:tt)+) => {
+        $crate::Resource::<$ty>::_from_embedded_bytes(include_bytes!($($file)+), $path)
     };
 
-    use crate::ReadFromFile;
+    (aligned $align:literal, $path:literal, $($file:tt)+) => {{
+        #[repr(C, align($align))]
+        struct Aligned<T: ?Sized>(T);
+        static DATA: &Aligned<[u8]> = &Aligned(*include_bytes!($($file)+));
+        static EMBEDDED: $crate::_Embedded<$crate::AlignedBytes> =
+            $crate::_Embedded::_new($crate::AlignedBytes::_new(&DATA.0), $path);
+        $crate::Resource::<$crate::AlignedBytes>::_from_embedded(&EMBEDDED)
+    }};
+}
 
-    /// A resource (string or binary) loaded in memory.
-    ///
-    /// In debug mode, this structure contains the data, the path to the file,
-    /// and a timestamp in order to support the `reload_if_changed` method.
-    ///
-    /// In release mode, it contains only an immutable, static reference to
-    /// the data.
-    ///
-    /// This struct implements `Deref` and `AsRef` (for the `&str`
-    /// and `&[u8]` types respectively) which allows you to refer
-    /// transparently to the data.
+/// Used internally to record an embedded file in the asset manifest.
+#[cfg(feature = "manifest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _register_embedded {
+    ($($file:tt)*) => {
+        $crate::_inventory::submit! {
+            $crate::ManifestEntry::_new($crate::_manifest_entry!($($file)*))
+        }
+    };
+}
+
+#[cfg(not(feature = "manifest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _register_embedded {
+    ($($file:tt)*) => {};
+}
+
+/// A type which can be loaded into a `Resource`, by decoding it from the
+/// contents of a file.
+///
+/// Implement this for your own types to load them as `Resource<T>`, with
+/// `Resource::open` or with the `as` form of the `resource!` macro. The
+/// resource can then be reloaded just like text and binary resources.
+///
+/// In debug mode, the value is read from the file. In release mode, the
+/// contents of the file are embedded in the binary, and the value is decoded
+/// from them each time the macro is evaluated.
+///
+/// Errors from decoding should use `std::io::ErrorKind::InvalidData`. When
+/// reloading fails, the previous value is kept.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io;
+///
+/// use resource::{resource, ReadFromFile, Resource};
+///
+/// #[derive(Clone)]
+/// struct Words(Vec<String>);
+///
+/// impl ReadFromFile for Words {
+///     fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+///         let text = std::str::from_utf8(bytes)
+///             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+///         Ok(Words(text.split_whitespace().map(str::to_owned).collect()))
+///     }
+/// }
+///
+/// let mut words = resource!("Cargo.toml" as Words);
+/// assert_eq!(words.0[0], "[package]");
+/// words.reload_if_changed();
+///
+/// let words = Resource::<Words>::open("Cargo.toml").unwrap();
+/// assert_eq!(words.0[0], "[package]");
+/// ```
+pub trait ReadFromFile: Sized {
+    /// Decodes the value from the contents of a file.
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self>;
+
+    /// Reads the value from a file.
     ///
-    /// Alternatively, it also implements `Into<Cow<'static, T>>`. In debug mode,
-    /// this will return a `Cow` that owns the data. In release mode, it returns
-    /// a `Cow` that borrows the static data.
-    pub struct Resource<B>(<B as ToOwned>::Owned, PathBuf, SystemTime)
-    where
-        B: 'static + ToOwned + ?Sized;
+    /// By default, this reads the whole file and passes it to `from_bytes`.
+    fn read_from_file(path: &Path) -> io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
 
-    impl<B> Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: ReadFromFile,
-    {
-        #[doc(hidden)]
-        /// Please don't call this directly. It has to be public for the macro
-        /// but you shouldn't call it because it's not stable.
-        pub fn _from_file(path: &str) -> Self {
-            let path = PathBuf::from(path);
-            let data = B::Owned::read_from_file(&path);
-            let modified = Self::modified(&path).unwrap_or(SystemTime::UNIX_EPOCH);
+impl ReadFromFile for String {
+    fn from_bytes(bytes: &[u8]) -> io::Result<String> {
+        String::from_utf8(bytes.to_vec()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
 
-            Resource(data, path, modified)
-        }
+    fn read_from_file(path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+}
 
-        fn modified(path: &Path) -> Option<SystemTime> {
-            std::fs::metadata(path)
-                .and_then(|metadata| metadata.modified())
-                .ok()
-        }
+impl ReadFromFile for Vec<u8> {
+    fn from_bytes(bytes: &[u8]) -> io::Result<Vec<u8>> {
+        Ok(bytes.to_vec())
+    }
 
-        /// Returns `true` if the resource has changed since loading.
-        ///
-        /// In release mode, always returns `false`.
-        pub fn changed(&self) -> bool {
-            let modified = Self::modified(&self.1);
-            modified.is_some() && modified != Some(self.2)
-        }
+    fn read_from_file(path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+}
 
-        /// Reloads the resource.
-        ///
-        /// In release mode, does nothing.
-        pub fn reload(&mut self) {
-            let data = B::Owned::read_from_file(&self.1);
-            let modified = Self::modified(&self.1).unwrap_or(SystemTime::UNIX_EPOCH);
-            self.0 = data;
-            self.2 = modified;
-        }
+/// The result of reloading a resource.
+///
+/// When a reload fails, the resource keeps serving the data from the last
+/// successful load.
+#[derive(Debug)]
+pub enum ReloadOutcome {
+    /// The resource was not reloaded, either because it has not changed or
+    /// because it was statically included.
+    Unchanged,
+
+    /// The resource was reloaded and now contains the new data.
+    Reloaded,
+
+    /// The resource could not be reloaded and still contains the old data.
+    Failed(ReloadError),
+}
 
-        /// Reloads the resource only if it has changed since the previous
-        /// load. Returns `true` if the resource was reloaded.
-        ///
-        /// In release mode, does nothing.
-        pub fn reload_if_changed(&mut self) -> bool {
-            let changed = self.changed();
-            if changed {
-                self.reload();
-            }
-            changed
+impl ReloadOutcome {
+    /// Returns `true` if the resource was reloaded.
+    pub fn is_reloaded(&self) -> bool {
+        matches!(self, ReloadOutcome::Reloaded)
+    }
+}
+
+/// The reason a resource could not be reloaded.
+#[derive(Debug)]
+pub enum ReloadError {
+    /// The file could not be read.
+    Io(std::io::Error),
+
+    /// The file was read, but the function transforming its contents failed.
+    Transform(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for ReloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReloadError::Io(e) => write!(f, "failed to read resource: {}", e),
+            ReloadError::Transform(e) => write!(f, "failed to transform resource: {}", e),
         }
     }
+}
 
-    impl<B> AsRef<B> for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: AsRef<B>,
-    {
-        fn as_ref(&self) -> &B {
-            self.0.as_ref()
+impl Error for ReloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReloadError::Io(e) => Some(e),
+            ReloadError::Transform(e) => Some(&**e),
         }
     }
+}
 
-    impl<B> Deref for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: AsRef<B>,
-    {
-        type Target = B;
+/// A resource (string or binary) loaded in memory.
+///
+/// A resource either refers to static data, as the macros produce in release
+/// mode, or owns data loaded from a file along with the path and modification
+/// time of the file, as the macros produce in debug mode. Only resources
+/// loaded from a file can change and be reloaded.
+///
+/// This struct implements `Deref` and `AsRef` (for the `&str`
+/// and `&[u8]` types respectively) which allows you to refer
+/// transparently to the data.
+///
+/// Alternatively, it also implements `Into<Cow<'static, T>>`, which returns a
+/// `Cow` that owns the data loaded from a file, or borrows the static data.
+///
+/// Data loaded from a file is reference counted, so cloning a resource is
+/// cheap and never copies the data. With the `bytes` feature, binary data
+/// can also be shared as `bytes::Bytes` without copying, using `to_bytes`.
+pub struct Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    data: Data<B>,
+}
 
-        fn deref(&self) -> &Self::Target {
-            self.as_ref()
+impl<B> Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    /// Creates a resource from static data. It never changes, and reloading
+    /// it does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resource::Resource;
+    ///
+    /// let greeting = Resource::<str>::from_static("Hello");
+    /// assert_eq!(&*greeting, "Hello");
+    /// assert!(!greeting.changed());
+    /// ```
+    pub const fn from_static(data: &'static B) -> Self {
+        Resource {
+            data: Data::Static(data),
         }
     }
 
-    // We don't want a `From` implementation because the `Resource`
-    // type itself is an implementation detail. Don't construct one!
-    #[allow(clippy::from_over_into)]
-    impl<B> Into<Cow<'static, B>> for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-    {
-        fn into(self) -> Cow<'static, B> {
-            Cow::Owned(self.0)
+    #[doc(hidden)]
+    /// Please don't call this directly. It has to be public for the macro
+    /// but you shouldn't call it because it's not stable.
+    pub const fn _from_embedded(embedded: &'static Embedded<B>) -> Self {
+        Resource {
+            data: Data::Embedded(embedded),
         }
     }
 
-    impl<B> Clone for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: Clone,
-    {
-        fn clone(&self) -> Self {
-            Resource(self.0.clone(), self.1.clone(), self.2)
+    fn from_file_data(
+        data: B::Owned,
+        size: usize,
+        path: PathBuf,
+        modified: Option<SystemTime>,
+    ) -> Self {
+        let file = FileState {
+            path,
+            modified,
+            loaded_at: SystemTime::now(),
+            generation: 0,
+        };
+        Resource {
+            data: Data::from_file(data, size, file),
         }
     }
-}
-
-#[cfg(any(
-    feature = "force-static",
-    all(not(feature = "force-dynamic"), not(debug_assertions))
-))]
-mod resource {
-    use std::{
-        borrow::{Cow, ToOwned},
-        convert::AsRef,
-        ops::Deref,
-    };
 
-    use crate::ReadFromFile;
+    /// Returns `true` if the resource was loaded from a file which has changed
+    /// since loading.
+    pub fn changed(&self) -> bool {
+        self.data.file().is_some_and(FileState::changed)
+    }
 
-    pub struct Resource<B>(&'static B)
-    where
-        B: 'static + ToOwned + ?Sized;
+    /// Returns the path of the file the resource was loaded from.
+    ///
+    /// For data embedded by the macros in release mode, this is the path the
+    /// file was embedded from at compile time, relative to the crate root, so
+    /// that it doesn't depend on where the crate was built. For data passed to
+    /// `from_static`, it is `None`.
+    pub fn path(&self) -> Option<&Path> {
+        self.data.path()
+    }
 
-    impl<B> Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: ReadFromFile,
-    {
-        #[doc(hidden)]
-        /// Please don't call this directly. It has to be public for the macro
-        /// but you shouldn't call it because it's not stable.
-        pub fn _from_data(data: &'static B) -> Self {
-            Resource(data)
-        }
+    /// Returns the size in bytes of the file the data was loaded or decoded
+    /// from.
+    ///
+    /// For data which didn't come from a file, such as static data, fallbacks
+    /// and deserialized resources, this is the size of the value itself.
+    pub fn len(&self) -> usize {
+        self.data.size()
+    }
 
-        pub fn changed(&self) -> bool {
-            false
-        }
+    /// Returns `true` if the file the data came from is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-        pub fn reload_if_changed(&mut self) -> bool {
-            false
-        }
+    /// Returns when the data was last loaded from a file, or `None` for
+    /// static data.
+    pub fn loaded_at(&self) -> Option<SystemTime> {
+        self.data.file().map(|file| file.loaded_at)
+    }
 
-        pub fn reload(&mut self) {}
+    /// Returns the modification time of the file when the data was last
+    /// loaded from it.
+    ///
+    /// Returns `None` for static data, if a fallback was used because the
+    /// file was missing, or if the platform doesn't support modification
+    /// times.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.data.file().and_then(|file| file.modified)
     }
 
-    impl<B> AsRef<B> for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: AsRef<B>,
-    {
-        fn as_ref(&self) -> &B {
-            self.0
-        }
+    /// Returns the number of times the resource has been successfully
+    /// reloaded. Static data is always generation 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resource::resource_str;
+    ///
+    /// let mut toml = resource_str!("Cargo.toml");
+    /// let generation = toml.generation();
+    ///
+    /// if toml.reload().is_reloaded() {
+    ///     assert_eq!(toml.generation(), generation + 1);
+    /// }
+    /// ```
+    pub fn generation(&self) -> u64 {
+        self.data.file().map_or(0, |file| file.generation)
     }
+}
 
-    impl<B> Deref for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: AsRef<B>,
-    {
-        type Target = B;
+impl<B> Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: ReadFromFile,
+{
+    /// Loads a resource from a file at runtime, in both debug and release
+    /// mode.
+    ///
+    /// Use this for files whose paths aren't known at compile time, such as
+    /// save files or user content. The resource can be reloaded just like one
+    /// loaded by the macros in debug mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resource::Resource;
+    ///
+    /// let mut toml = Resource::<str>::open("Cargo.toml").unwrap();
+    /// assert!(toml.contains("[package]"));
+    /// toml.reload_if_changed();
+    ///
+    /// assert!(Resource::<[u8]>::open("missing.bin").is_err());
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let (data, modified, size) = read_complete(path)?;
+        Ok(Resource::from_file_data(
+            data,
+            size,
+            path.to_owned(),
+            modified,
+        ))
+    }
 
-        fn deref(&self) -> &Self::Target {
-            self.0
-        }
+    /// Reloads the resource.
+    ///
+    /// If the file can't be read, or keeps changing while it is being read,
+    /// the previous data is kept and `ReloadOutcome::Failed` is returned.
+    ///
+    /// Resources created from static data, as the macros do in release mode,
+    /// aren't reloaded, and `ReloadOutcome::Unchanged` is returned.
+    pub fn reload(&mut self) -> ReloadOutcome {
+        let Some(file) = self.data.file() else {
+            return ReloadOutcome::Unchanged;
+        };
+
+        let result = reread_complete(&file.path);
+        self.apply_reload(result)
     }
 
-    // We don't want a `From` implementation because the `Resource`
-    // type itself is an implementation detail. Don't construct one!
-    #[allow(clippy::from_over_into)]
-    impl<B> Into<Cow<'static, B>> for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-    {
-        fn into(self) -> Cow<'static, B> {
-            Cow::Borrowed(self.0)
+    fn apply_reload(&mut self, result: ReadResult<B::Owned>) -> ReloadOutcome {
+        let Some(file) = self.data.file() else {
+            return ReloadOutcome::Unchanged;
+        };
+
+        match result {
+            Ok((data, modified, size)) => {
+                let file = FileState {
+                    path: file.path.clone(),
+                    modified,
+                    loaded_at: SystemTime::now(),
+                    generation: file.generation + 1,
+                };
+                self.data = Data::from_file(data, size, file);
+                ReloadOutcome::Reloaded
+            }
+            Err(e) => ReloadOutcome::Failed(ReloadError::Io(e)),
         }
     }
 
-    impl<B> Clone for Resource<B>
-    where
-        B: 'static + ToOwned + ?Sized,
-        B::Owned: Clone,
-    {
-        fn clone(&self) -> Self {
-            Resource(self.0)
+    /// Reloads the resource only if it has changed since the previous load.
+    ///
+    /// Changes are only picked up once the file has gone unmodified for a
+    /// short time, so that a file which is still being written isn't read.
+    ///
+    /// Resources created from static data, as the macros do in release mode,
+    /// aren't reloaded, and `ReloadOutcome::Unchanged` is returned.
+    pub fn reload_if_changed(&mut self) -> ReloadOutcome {
+        match self.data.file() {
+            Some(file) if file.changed_and_settled() => self.reload(),
+            _ => ReloadOutcome::Unchanged,
         }
     }
 }
 
-/// Load text resources statically in release mode, or dynamically in debug.
-///
-/// The filenames are relative to the root of your crate.
-///
-/// If you wish to override the static or dynamic behaviour, you can use the
-/// `force-static` or `force-dynamic` features.
-///
-/// This macro optionally takes a function which can be used to transform the
-/// contents of each file on load.
-///
-/// # Panics
-///
-/// When dynamically including, this will panic if any file does not exist. When
-/// statically including, this will be a compile error and will never panic.
-///
-/// # Examples
-///
-/// Load a single text file:
-///
-/// ```rust
-/// use resource::resource_str;
-///
-/// let toml = resource_str!("Cargo.toml");
-/// assert!(toml.contains("[package]"));
-/// ```
-///
-/// Load an array or tuple of text files:
-///
-/// ```rust
-/// use resource::resource_str;
-///
-/// let (toml, lib) = resource_str!(("Cargo.toml", "src/lib.rs"));
-/// let as_array = resource_str!(["Cargo.toml", "src/lib.rs"]);
-///
-/// assert_eq!(toml.as_ref(), as_array[0].as_ref());
-/// assert_eq!(lib.as_ref(), as_array[1].as_ref());
-/// ```
-///
-/// Load multiple text files and apply a transformation to each one:
-///
-/// ```rust
-/// use resource::resource_str;
-///
-/// let [toml, lib] = resource_str!(["Cargo.toml", "src/lib.rs"], str::to_uppercase);
-///
-/// assert!(toml.contains("RESOURCE"));
-/// assert!(lib.contains("MACRO_RULES"));
-/// ```
+impl<B> Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: Send + Sync + 'static,
+{
+    /// Returns the data as a static reference, for APIs which require
+    /// `'static` data.
+    ///
+    /// Static data, as the macros produce in release mode, is returned as is.
+    /// Data loaded from a file is leaked instead. Leaking the same file again
+    /// while it is unchanged returns the same reference, but each change to
+    /// the file leaks a new copy, so avoid calling this on every reload.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total size of the leaked data would exceed the limit set
+    /// with `set_leak_limit`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resource::resource_str;
+    ///
+    /// let name: &'static str = resource_str!("tests/str.txt").into_static();
+    /// assert_eq!(name, "This\nis\na\nstring\n");
+    /// ```
+    pub fn into_static(self) -> &'static B {
+        if let Some(data) = self.data.as_static() {
+            return data;
+        }
+
+        let size = self.data.size();
+        let file = self
+            .data
+            .file()
+            .and_then(|file| Some((file.path.clone(), file.modified?)));
+        let file = file
+            .as_ref()
+            .map(|(path, modified)| (path.as_path(), *modified));
+
+        leak::leak(file, size, || match self.data.into_cow() {
+            Cow::Owned(data) => data,
+            Cow::Borrowed(data) => data.to_owned(),
+        })
+        .borrow()
+    }
+}
+
+impl<B> Resource<B>
+where
+    B: 'static + ToOwned + AsRef<[u8]> + ?Sized,
+{
+    /// Returns a seekable reader over the data, for decoders which take
+    /// `impl Read + Seek`.
+    ///
+    /// The reader borrows the data already in memory, so it always matches
+    /// the resource, even if the file has since changed. To stream a large
+    /// file without loading it first, use the `resource_reader!` macro.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::BufRead;
+    ///
+    /// use resource::resource_str;
+    ///
+    /// let toml = resource_str!("Cargo.toml");
+    /// let first_line = toml.reader().lines().next().unwrap().unwrap();
+    /// assert_eq!(first_line, "[package]");
+    /// ```
+    pub fn reader(&self) -> ResourceReader<'_> {
+        ResourceReader::from_bytes((*self.data).as_ref())
+    }
+}
+
+impl<B> AsRef<B> for Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    fn as_ref(&self) -> &B {
+        &self.data
+    }
+}
+
+impl<B> Deref for Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    type Target = B;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+// We don't want a `From` implementation because the `Resource`
+// type itself is an implementation detail. Don't construct one!
+#[allow(clippy::from_over_into)]
+impl<B> Into<Cow<'static, B>> for Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    fn into(self) -> Cow<'static, B> {
+        self.data.into_cow()
+    }
+}
+
+impl<B> Clone for Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    fn clone(&self) -> Self {
+        Resource {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<B> Borrow<B> for Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    fn borrow(&self) -> &B {
+        &self.data
+    }
+}
+
+// Only the path and size are shown, as the data may be large
+impl<B> fmt::Debug for Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Resource")
+            .field("path", &self.path())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<B> fmt::Display for Resource<B>
+where
+    B: 'static + ToOwned + fmt::Display + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&*self.data, f)
+    }
+}
+
+// The comparison traits only look at the data, consistently with `Borrow`, so
+// a resource compares the same way whether it was loaded or embedded.
+
+impl<B> PartialEq for Resource<B>
+where
+    B: 'static + ToOwned + PartialEq + ?Sized,
+{
+    fn eq(&self, other: &Self) -> bool {
+        *self.data == *other.data
+    }
+}
+
+impl<B> Eq for Resource<B> where B: 'static + ToOwned + Eq + ?Sized {}
+
+impl<B> PartialOrd for Resource<B>
+where
+    B: 'static + ToOwned + PartialOrd + ?Sized,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (*self.data).partial_cmp(&*other.data)
+    }
+}
+
+impl<B> Ord for Resource<B>
+where
+    B: 'static + ToOwned + Ord + ?Sized,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self.data).cmp(&*other.data)
+    }
+}
+
+impl<B> Hash for Resource<B>
+where
+    B: 'static + ToOwned + Hash + ?Sized,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.data).hash(state)
+    }
+}
+
 #[cfg(any(
     feature = "force-dynamic",
     all(not(feature = "force-static"), debug_assertions)
 ))]
-#[macro_export]
-macro_rules! resource_str {
-    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
-        [ $(resource_str!($filenames, $load_fn)),* ]
+mod resource_impl {
+    use std::{
+        borrow::{Borrow, ToOwned},
+        convert::AsRef,
+        io,
+        ops::Deref,
+        path::{Path, PathBuf},
+        sync::{OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
     };
 
-    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
-        ( $(resource_str!($filenames, $load_fn)),* )
-    };
+    use crate::{LoadErrors, ReadFromFile, ReloadOutcome, Resource};
+
+    impl<B> Resource<B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: ReadFromFile + 'static,
+    {
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub fn _from_file(path: &str) -> Self {
+            Resource::open_or_fallback(Path::new(path))
+                .unwrap_or_else(|e| panic!("Failed to read `{}`: {}", path, e))
+        }
+
+        fn open_or_fallback(path: &Path) -> io::Result<Self> {
+            match Resource::open(path) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    let data = crate::fallback::fallback::<B::Owned>(path).ok_or(e)?;
+                    let size = std::mem::size_of_val::<B>(data.borrow());
+
+                    // The file will count as changed as soon as it exists
+                    Ok(Resource::from_file_data(data, size, path.to_owned(), None))
+                }
+                result => result,
+            }
+        }
+    }
+
+    impl<B> Resource<B>
+    where
+        B: 'static + ToOwned + Sync + ?Sized,
+        B::Owned: ReadFromFile + Send + Sync + 'static,
+    {
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub fn _from_files<const N: usize>(paths: [&str; N]) -> [Self; N] {
+            Resource::_try_from_files(paths).unwrap_or_else(|e| panic!("{}", e))
+        }
+
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub fn _try_from_files<const N: usize>(paths: [&str; N]) -> Result<[Self; N], LoadErrors> {
+            let results = crate::parallel::load_all(&paths, Resource::open_or_fallback);
+            let resources = LoadErrors::collect(paths.iter().map(PathBuf::from).zip(results))?;
+
+            match resources.try_into() {
+                Ok(resources) => Ok(resources),
+                Err(_) => unreachable!("one resource is loaded for each path"),
+            }
+        }
+
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub fn _from_named_files<const N: usize>(
+            files: [(&'static str, &str); N],
+        ) -> [(&'static str, Self); N] {
+            Resource::_try_from_named_files(files).unwrap_or_else(|e| panic!("{}", e))
+        }
+
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub fn _try_from_named_files<const N: usize>(
+            files: [(&'static str, &str); N],
+        ) -> Result<[(&'static str, Self); N], LoadErrors> {
+            let mut resources = Resource::_try_from_files(files.map(|(_, path)| path))?.into_iter();
+            Ok(files.map(|(name, _)| (name, resources.next().unwrap())))
+        }
+    }
+
+    /// A resource which can be stored in a `static` item.
+    ///
+    /// In debug mode, the file is loaded the first time the resource is
+    /// accessed, and can be reloaded through a shared reference.
+    ///
+    /// In release mode, it contains only an immutable, static reference to
+    /// the data.
+    ///
+    /// Use the `get` method to access the data, which loads it on first
+    /// access. `LazyResource` doesn't implement `Deref` itself, because a
+    /// plain reference to the data could outlive a reload which replaces it.
+    /// The guard returned by `get` keeps the data alive, and reloading waits
+    /// until it is dropped.
+    pub struct LazyResource<B>(&'static str, OnceLock<RwLock<Resource<B>>>)
+    where
+        B: 'static + ToOwned + ?Sized;
+
+    impl<B> LazyResource<B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: ReadFromFile + 'static,
+    {
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub const fn _from_file(path: &'static str) -> Self {
+            LazyResource(path, OnceLock::new())
+        }
+
+        fn resource(&self) -> &RwLock<Resource<B>> {
+            self.1
+                .get_or_init(|| RwLock::new(Resource::_from_file(self.0)))
+        }
+
+        fn read(&self) -> RwLockReadGuard<'_, Resource<B>> {
+            self.resource()
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+        }
+
+        fn write(&self) -> RwLockWriteGuard<'_, Resource<B>> {
+            self.resource()
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+        }
+
+        /// Returns a guard which dereferences to the data, loading it first
+        /// if this is the first access.
+        ///
+        /// In debug mode, reloading the resource blocks while any guards are
+        /// alive, so avoid holding on to them for long.
+        pub fn get(&self) -> LazyResourceGuard<'_, B> {
+            LazyResourceGuard(self.read())
+        }
+
+        /// Returns `true` if the resource has changed since loading.
+        ///
+        /// In release mode, always returns `false`.
+        pub fn changed(&self) -> bool {
+            self.read().changed()
+        }
+
+        /// Reloads the resource.
+        ///
+        /// In release mode, does nothing and returns `ReloadOutcome::Unchanged`.
+        pub fn reload(&self) -> ReloadOutcome {
+            self.write().reload()
+        }
+
+        /// Reloads the resource only if it has changed since the previous
+        /// load.
+        ///
+        /// In release mode, does nothing and returns `ReloadOutcome::Unchanged`.
+        pub fn reload_if_changed(&self) -> ReloadOutcome {
+            self.write().reload_if_changed()
+        }
+    }
+
+    /// Provides access to the data of a `LazyResource`.
+    ///
+    /// Returned by `LazyResource::get`.
+    pub struct LazyResourceGuard<'a, B>(RwLockReadGuard<'a, Resource<B>>)
+    where
+        B: 'static + ToOwned + ?Sized;
+
+    impl<B> AsRef<B> for LazyResourceGuard<'_, B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: AsRef<B>,
+    {
+        fn as_ref(&self) -> &B {
+            self.0.as_ref()
+        }
+    }
+
+    impl<B> Deref for LazyResourceGuard<'_, B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: AsRef<B>,
+    {
+        type Target = B;
+
+        fn deref(&self) -> &Self::Target {
+            self.as_ref()
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+mod resource_impl {
+    use std::{borrow::ToOwned, convert::AsRef, marker::PhantomData, ops::Deref};
+
+    use crate::{ReadFromFile, ReloadOutcome, Resource, shared::Data};
+
+    impl<B> Resource<B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: ReadFromFile,
+    {
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub fn _from_embedded_bytes(bytes: &'static [u8], source: &'static str) -> Self {
+            let data = B::Owned::from_bytes(bytes)
+                .unwrap_or_else(|e| panic!("Failed to decode `{}`: {}", source, e));
+
+            Resource {
+                data: Data::decoded(data, bytes.len(), Some(source)),
+            }
+        }
+    }
+
+    /// A resource which can be stored in a `static` item.
+    ///
+    /// In debug mode, the file is loaded the first time the resource is
+    /// accessed, and can be reloaded through a shared reference.
+    ///
+    /// In release mode, it contains only an immutable, static reference to
+    /// the data.
+    ///
+    /// Use the `get` method to access the data, which loads it on first
+    /// access. `LazyResource` doesn't implement `Deref` itself, because a
+    /// plain reference to the data could outlive a reload which replaces it.
+    /// The guard returned by `get` keeps the data alive, and reloading waits
+    /// until it is dropped.
+    pub struct LazyResource<B>(&'static B)
+    where
+        B: 'static + ToOwned + ?Sized;
+
+    impl<B> LazyResource<B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: ReadFromFile,
+    {
+        #[doc(hidden)]
+        /// Please don't call this directly. It has to be public for the macro
+        /// but you shouldn't call it because it's not stable.
+        pub const fn _from_data(data: &'static B) -> Self {
+            LazyResource(data)
+        }
+
+        /// Returns a guard which dereferences to the data, loading it first
+        /// if this is the first access.
+        ///
+        /// In debug mode, reloading the resource blocks while any guards are
+        /// alive, so avoid holding on to them for long.
+        pub fn get(&self) -> LazyResourceGuard<'_, B> {
+            LazyResourceGuard(self.0, PhantomData)
+        }
+
+        /// Returns `true` if the resource has changed since loading.
+        ///
+        /// In release mode, always returns `false`.
+        pub fn changed(&self) -> bool {
+            false
+        }
+
+        /// Reloads the resource only if it has changed since the previous
+        /// load.
+        ///
+        /// In release mode, does nothing and returns `ReloadOutcome::Unchanged`.
+        pub fn reload_if_changed(&self) -> ReloadOutcome {
+            ReloadOutcome::Unchanged
+        }
+
+        /// Reloads the resource.
+        ///
+        /// In release mode, does nothing and returns `ReloadOutcome::Unchanged`.
+        pub fn reload(&self) -> ReloadOutcome {
+            ReloadOutcome::Unchanged
+        }
+    }
+
+    /// Provides access to the data of a `LazyResource`.
+    ///
+    /// Returned by `LazyResource::get`.
+    pub struct LazyResourceGuard<'a, B>(&'static B, PhantomData<&'a ()>)
+    where
+        B: 'static + ToOwned + ?Sized;
+
+    impl<B> AsRef<B> for LazyResourceGuard<'_, B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: AsRef<B>,
+    {
+        fn as_ref(&self) -> &B {
+            self.0
+        }
+    }
+
+    impl<B> Deref for LazyResourceGuard<'_, B>
+    where
+        B: 'static + ToOwned + ?Sized,
+        B::Owned: AsRef<B>,
+    {
+        type Target = B;
+
+        fn deref(&self) -> &Self::Target {
+            self.0
+        }
+    }
+}
+
+/// Load text resources statically in release mode, or dynamically in debug.
+///
+/// The filenames are relative to the root of your crate. Prefix a filename
+/// with `out_dir:` to load a file generated by your build script, relative
+/// to `OUT_DIR`, or with `package name,` to load a file relative to the root
+/// of another crate in your workspace.
+///
+/// If you wish to override the static or dynamic behaviour, you can use the
+/// `force-static` or `force-dynamic` features.
+///
+/// This macro optionally takes a function which can be used to transform the
+/// contents of each file on load.
+///
+/// # Panics
+///
+/// When dynamically including, this will panic if any file does not exist,
+/// unless a fallback has been set with `set_fallback`. The files in an array
+/// are read in parallel, and the panic lists every file which couldn't be
+/// read. When statically including, this will be a compile error and will
+/// never panic.
+///
+/// Prefix an array with `try` to get a `Result` instead of panicking, with a
+/// `LoadErrors` listing every file which couldn't be read. When statically
+/// including, it is always `Ok`.
+///
+/// # Examples
+///
+/// Load a single text file:
+///
+/// ```rust
+/// use resource::resource_str;
+///
+/// let toml = resource_str!("Cargo.toml");
+/// assert!(toml.contains("[package]"));
+/// ```
+///
+/// Load an array or tuple of text files:
+///
+/// ```rust
+/// use resource::resource_str;
+///
+/// let (toml, lib) = resource_str!(("Cargo.toml", "src/lib.rs"));
+/// let as_array = resource_str!(["Cargo.toml", "src/lib.rs"]);
+///
+/// assert_eq!(toml.as_ref(), as_array[0].as_ref());
+/// assert_eq!(lib.as_ref(), as_array[1].as_ref());
+/// ```
+///
+/// Load an array of text files, returning every error instead of panicking:
+///
+/// ```rust
+/// use resource::resource_str;
+///
+/// let [toml, lib] = resource_str!(try ["Cargo.toml", "src/lib.rs"]).unwrap();
+/// assert!(toml.contains("[package]"));
+/// ```
+///
+/// Load multiple text files and apply a transformation to each one:
+///
+/// ```rust
+/// use resource::resource_str;
+///
+/// let [toml, lib] = resource_str!(["Cargo.toml", "src/lib.rs"], str::to_uppercase);
+///
+/// assert!(toml.contains("RESOURCE"));
+/// assert!(lib.contains("MACRO_RULES"));
+/// ```
+///
+/// Load a file generated by your build script:
+///
+/// ```rust,ignore
+/// use resource::resource_str;
+///
+/// let tables = resource_str!(out_dir: "generated/tables.txt");
+/// ```
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[macro_export]
+macro_rules! resource_str {
+    (try [ $($filenames:tt),* $(,)* ]) => {
+        $crate::Resource::<str>::_try_from_files([ $($crate::_checked_path!($filenames)),* ])
+    };
+
+    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
+        $crate::Resource::<str>::_from_files([ $($crate::_checked_path!($filenames)),* ])
+            .map(|resource| $load_fn(<$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&resource)))
+    };
+
+    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
+        ( $($crate::resource_str!($filenames, $load_fn)),* )
+    };
+
+    ([ $($filenames:tt),* $(,)* ]) => {
+        $crate::Resource::<str>::_from_files([ $($crate::_checked_path!($filenames)),* ])
+    };
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource_str!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&$crate::resource_str!(package $package, $filename))
+        )
+    };
+
+    (package $package:ident, $filename:tt) => {
+        $crate::Resource::<str>::_from_file($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&$crate::resource_str!(out_dir: $filename))
+        )
+    };
+
+    (out_dir: $filename:tt) => {
+        $crate::Resource::<str>::_from_file($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&$crate::resource_str!($filename))
+        )
+    };
+
+    ($filename:tt) => {
+        $crate::Resource::<str>::_from_file($crate::_checked_path!($filename))
+    };
+}
+
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[macro_export]
+macro_rules! resource_str {
+    (try [ $($filenames:tt),* $(,)* ]) => {
+        ::std::result::Result::<_, $crate::LoadErrors>::Ok($crate::resource_str!([ $($filenames),* ]))
+    };
+
+    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
+        $crate::resource_str!([ $($filenames),* ])
+            .map(|resource| $load_fn(<$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&resource)))
+    };
+
+    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
+        ( $($crate::resource_str!($filenames, $load_fn)),* )
+    };
+
+    ([ $($filenames:tt),* $(,)* ]) => {{
+        let resources = [ $($crate::resource_str!($filenames)),* ];
+        $crate::LoadProgress::_report_embedded(resources.iter().map(|resource| resource.len()));
+        resources
+    }};
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource_str!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&$crate::resource_str!(package $package, $filename))
+        )
+    };
+
+    (package $package:ident, $filename:tt) => {{
+        $crate::_register_embedded!(package $package, $filename);
+        $crate::_checked_path!(package $package, $filename => $crate::_embed!(str))
+    }};
+
+    (out_dir: $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&$crate::resource_str!(out_dir: $filename))
+        )
+    };
+
+    (out_dir: $filename:tt) => {{
+        $crate::_register_embedded!(out_dir: $filename);
+        $crate::_checked_path!(out_dir: $filename => $crate::_embed!(str))
+    }};
+
+    ($filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<str> as ::std::convert::AsRef<str>>::as_ref(&$crate::resource_str!($filename))
+        )
+    };
+
+    ($filename:tt) => {{
+        $crate::_register_embedded!($filename);
+        $crate::_checked_path!($filename => $crate::_embed!(str))
+    }};
+}
+
+/// Load binary resources statically in release mode, or dynamically in
+/// debug.
+///
+/// The filenames are relative to the root of your crate. Prefix a filename
+/// with `out_dir:` to load a file generated by your build script, relative
+/// to `OUT_DIR`, or with `package name,` to load a file relative to the root
+/// of another crate in your workspace.
+///
+/// If you wish to override the static or dynamic behaviour, you can use the
+/// `force-static` or `force-dynamic` features.
+///
+/// This macro optionally takes a function which can be used to transform the
+/// contents of each file on load.
+///
+/// # Panics
+///
+/// When dynamically including, this will panic if any file does not exist,
+/// unless a fallback has been set with `set_fallback`. The files in an array
+/// are read in parallel, and the panic lists every file which couldn't be
+/// read. When statically including, this will be a compile error and will
+/// never panic.
+///
+/// Prefix an array with `try` to get a `Result` instead of panicking, with a
+/// `LoadErrors` listing every file which couldn't be read. When statically
+/// including, it is always `Ok`.
+///
+/// # Examples
+///
+/// Load a single binary file:
+///
+/// ```rust
+/// use resource::resource;
+///
+/// let toml = resource!("Cargo.toml");
+/// assert_eq!(&toml[0..9], b"[package]");
+/// ```
+///
+/// Load an array or tuple of binary files:
+///
+/// ```rust
+/// use resource::resource;
+///
+/// let (toml, lib) = resource!(("Cargo.toml", "src/lib.rs"));
+/// let as_array = resource!(["Cargo.toml", "src/lib.rs"]);
+///
+/// assert_eq!(toml.as_ref(), as_array[0].as_ref());
+/// assert_eq!(lib.as_ref(), as_array[1].as_ref());
+/// ```
+///
+/// Load an array of binary files, returning every error instead of panicking:
+///
+/// ```rust
+/// use resource::resource;
+///
+/// let [toml, lib] = resource!(try ["Cargo.toml", "src/lib.rs"]).unwrap();
+/// assert_eq!(&toml[0..9], b"[package]");
+/// ```
+///
+/// Load binary files and apply a transformation to each one:
+///
+/// ```rust
+/// use resource::resource;
+///
+/// let [toml, lib] = resource!(["Cargo.toml", "src/lib.rs"],
+///     |bytes: &[u8]| bytes.to_ascii_uppercase());
+///
+/// assert_eq!(&toml[0..9], b"[PACKAGE]");
+/// assert_eq!(&lib[0..4], b"//! ");
+/// ```
+///
+/// Load a file generated by your build script:
+///
+/// ```rust,ignore
+/// use resource::resource;
+///
+/// let shader = resource!(out_dir: "shaders/main.spv");
+/// ```
+///
+/// Load binary data aligned to 16 bytes, and view it as floats without
+/// copying:
+///
+/// ```rust,ignore
+/// use resource::resource;
+///
+/// let mesh = resource!("assets/mesh.bin", align = 16);
+/// let vertices: &[[f32; 4]] = mesh.as_slice().unwrap();
+/// ```
+///
+/// Load a file as your own type, which implements `ReadFromFile`:
+///
+/// ```rust,ignore
+/// use resource::resource;
+///
+/// let logo = resource!("assets/logo.png" as Image);
+/// ```
+///
+/// Load a file from another crate in your workspace:
+///
+/// ```rust,ignore
+/// use resource::resource;
+///
+/// let font = resource!(package engine_assets, "fonts/ui.ttf");
+/// ```
+///
+/// Missing files are a compile error in both debug and release mode:
+///
+/// ```rust,compile_fail
+/// use resource::resource;
+///
+/// let logo = resource!("assets/lgoo.png");
+/// ```
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[macro_export]
+macro_rules! resource {
+    (try [ $($filenames:tt),* $(,)* ]) => {
+        $crate::Resource::<[u8]>::_try_from_files([ $($crate::_checked_path!($filenames)),* ])
+    };
+
+    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
+        $crate::Resource::<[u8]>::_from_files([ $($crate::_checked_path!($filenames)),* ])
+            .map(|resource| $load_fn(<$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&resource)))
+    };
+
+    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
+        ( $($crate::resource!($filenames, $load_fn)),* )
+    };
+
+    ([ $($filenames:tt),* $(,)* ]) => {
+        $crate::Resource::<[u8]>::_from_files([ $($crate::_checked_path!($filenames)),* ])
+    };
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt as $ty:ty) => {
+        $crate::Resource::<$ty>::_from_file($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt as $ty:ty) => {
+        $crate::Resource::<$ty>::_from_file($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt as $ty:ty) => {
+        $crate::Resource::<$ty>::_from_file($crate::_checked_path!($filename))
+    };
+
+    (package $package:ident, $filename:tt, align = $align:literal) => {{
+        $crate::_check_align!($align);
+        $crate::Resource::<$crate::AlignedBytes>::_from_file($crate::_checked_path!(package $package, $filename))
+    }};
+
+    (out_dir: $filename:tt, align = $align:literal) => {{
+        $crate::_check_align!($align);
+        $crate::Resource::<$crate::AlignedBytes>::_from_file($crate::_checked_path!(out_dir: $filename))
+    }};
+
+    ($filename:tt, align = $align:literal) => {{
+        $crate::_check_align!($align);
+        $crate::Resource::<$crate::AlignedBytes>::_from_file($crate::_checked_path!($filename))
+    }};
+
+    (package $package:ident, $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&$crate::resource!(package $package, $filename))
+        )
+    };
+
+    (package $package:ident, $filename:tt) => {
+        $crate::Resource::<[u8]>::_from_file($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&$crate::resource!(out_dir: $filename))
+        )
+    };
+
+    (out_dir: $filename:tt) => {
+        $crate::Resource::<[u8]>::_from_file($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&$crate::resource!($filename))
+        )
+    };
+
+    ($filename:tt) => {
+        $crate::Resource::<[u8]>::_from_file($crate::_checked_path!($filename))
+    };
+}
+
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[macro_export]
+macro_rules! resource {
+    (try [ $($filenames:tt),* $(,)* ]) => {
+        ::std::result::Result::<_, $crate::LoadErrors>::Ok($crate::resource!([ $($filenames),* ]))
+    };
+
+    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
+        $crate::resource!([ $($filenames),* ])
+            .map(|resource| $load_fn(<$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&resource)))
+    };
+
+    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
+        ( $($crate::resource!($filenames, $load_fn)),* )
+    };
+
+    ([ $($filenames:tt),* $(,)* ]) => {{
+        let resources = [ $($crate::resource!($filenames)),* ];
+        $crate::LoadProgress::_report_embedded(resources.iter().map(|resource| resource.len()));
+        resources
+    }};
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt as $ty:ty) => {{
+        $crate::_register_embedded!(package $package, $filename);
+        $crate::_checked_path!(package $package, $filename => $crate::_embed!(decode $ty))
+    }};
+
+    (out_dir: $filename:tt as $ty:ty) => {{
+        $crate::_register_embedded!(out_dir: $filename);
+        $crate::_checked_path!(out_dir: $filename => $crate::_embed!(decode $ty))
+    }};
+
+    ($filename:tt as $ty:ty) => {{
+        $crate::_register_embedded!($filename);
+        $crate::_checked_path!($filename => $crate::_embed!(decode $ty))
+    }};
+
+    (package $package:ident, $filename:tt, align = $align:literal) => {{
+        $crate::_check_align!($align);
+        $crate::_register_embedded!(package $package, $filename);
+        $crate::_checked_path!(package $package, $filename => $crate::_embed!(aligned $align))
+    }};
+
+    (out_dir: $filename:tt, align = $align:literal) => {{
+        $crate::_check_align!($align);
+        $crate::_register_embedded!(out_dir: $filename);
+        $crate::_checked_path!(out_dir: $filename => $crate::_embed!(aligned $align))
+    }};
+
+    ($filename:tt, align = $align:literal) => {{
+        $crate::_check_align!($align);
+        $crate::_register_embedded!($filename);
+        $crate::_checked_path!($filename => $crate::_embed!(aligned $align))
+    }};
+
+    (package $package:ident, $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&$crate::resource!(package $package, $filename))
+        )
+    };
+
+    (package $package:ident, $filename:tt) => {{
+        $crate::_register_embedded!(package $package, $filename);
+        $crate::_checked_path!(package $package, $filename => $crate::_embed!(bytes))
+    }};
+
+    (out_dir: $filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&$crate::resource!(out_dir: $filename))
+        )
+    };
+
+    (out_dir: $filename:tt) => {{
+        $crate::_register_embedded!(out_dir: $filename);
+        $crate::_checked_path!(out_dir: $filename => $crate::_embed!(bytes))
+    }};
+
+    ($filename:tt, $load_fn:expr) => {
+        $load_fn(
+            <$crate::Resource<[u8]> as ::std::convert::AsRef<[u8]>>::as_ref(&$crate::resource!($filename))
+        )
+    };
+
+    ($filename:tt) => {{
+        $crate::_register_embedded!($filename);
+        $crate::_checked_path!($filename => $crate::_embed!(bytes))
+    }};
+}
+
+/// Load text resources into a `static` item, statically in release mode, or
+/// lazily and dynamically in debug.
+///
+/// The filenames are relative to the root of your crate, to `OUT_DIR` when
+/// prefixed with `out_dir:`, or to another crate in your workspace when
+/// prefixed with `package name,`.
+///
+/// In debug mode, each file is loaded the first time it is accessed. Unlike
+/// `resource_str!`, this macro does not take a transformation function.
+///
+/// # Panics
+///
+/// When dynamically including, accessing the resource will panic if the file
+/// does not exist, unless a fallback has been set with `set_fallback`. When
+/// statically including, this will be a compile error and will never panic.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::{resource_str_static, LazyResource};
+///
+/// static TOML: LazyResource<str> = resource_str_static!("Cargo.toml");
+/// static SOURCES: [LazyResource<str>; 2] = resource_str_static!(["Cargo.toml", "src/lib.rs"]);
+///
+/// assert!(TOML.get().contains("[package]"));
+/// assert_eq!(&*TOML.get(), &*SOURCES[0].get());
+/// ```
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[macro_export]
+macro_rules! resource_str_static {
+    ([ $($filenames:tt),* $(,)* ]) => {
+        [ $($crate::resource_str_static!($filenames)),* ]
+    };
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource_str_static!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt) => {
+        $crate::LazyResource::<str>::_from_file($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt) => {
+        $crate::LazyResource::<str>::_from_file($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt) => {
+        $crate::LazyResource::<str>::_from_file($crate::_checked_path!($filename))
+    };
+}
+
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[macro_export]
+macro_rules! resource_str_static {
+    ([ $($filenames:tt),* $(,)* ]) => {
+        [ $($crate::resource_str_static!($filenames)),* ]
+    };
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource_str_static!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt) => {{
+        $crate::_register_embedded!(package $package, $filename);
+        $crate::LazyResource::<str>::_from_data(include_str!($crate::_checked_path!(package $package, $filename)))
+    }};
+
+    (out_dir: $filename:tt) => {{
+        $crate::_register_embedded!(out_dir: $filename);
+        $crate::LazyResource::<str>::_from_data(include_str!($crate::_checked_path!(out_dir: $filename)))
+    }};
+
+    ($filename:tt) => {{
+        $crate::_register_embedded!($filename);
+        $crate::LazyResource::<str>::_from_data(include_str!($crate::_checked_path!($filename)))
+    }};
+}
+
+/// Load binary resources into a `static` item, statically in release mode, or
+/// lazily and dynamically in debug.
+///
+/// The filenames are relative to the root of your crate, to `OUT_DIR` when
+/// prefixed with `out_dir:`, or to another crate in your workspace when
+/// prefixed with `package name,`.
+///
+/// In debug mode, each file is loaded the first time it is accessed. Unlike
+/// `resource!`, this macro does not take a transformation function.
+///
+/// # Panics
+///
+/// When dynamically including, accessing the resource will panic if the file
+/// does not exist, unless a fallback has been set with `set_fallback`. When
+/// statically including, this will be a compile error and will never panic.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::{resource_static, LazyResource};
+///
+/// static TOML: LazyResource<[u8]> = resource_static!("Cargo.toml");
+///
+/// assert_eq!(&TOML.get()[0..9], b"[package]");
+/// ```
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[macro_export]
+macro_rules! resource_static {
+    ([ $($filenames:tt),* $(,)* ]) => {
+        [ $($crate::resource_static!($filenames)),* ]
+    };
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource_static!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt) => {
+        $crate::LazyResource::<[u8]>::_from_file($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt) => {
+        $crate::LazyResource::<[u8]>::_from_file($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt) => {
+        $crate::LazyResource::<[u8]>::_from_file($crate::_checked_path!($filename))
+    };
+}
+
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[macro_export]
+macro_rules! resource_static {
+    ([ $($filenames:tt),* $(,)* ]) => {
+        [ $($crate::resource_static!($filenames)),* ]
+    };
+
+    (( $($filenames:tt),* $(,)* )) => {
+        ( $($crate::resource_static!($filenames)),* )
+    };
+
+    (package $package:ident, $filename:tt) => {{
+        $crate::_register_embedded!(package $package, $filename);
+        $crate::LazyResource::<[u8]>::_from_data(include_bytes!($crate::_checked_path!(package $package, $filename)))
+    }};
+
+    (out_dir: $filename:tt) => {{
+        $crate::_register_embedded!(out_dir: $filename);
+        $crate::LazyResource::<[u8]>::_from_data(include_bytes!($crate::_checked_path!(out_dir: $filename)))
+    }};
+
+    ($filename:tt) => {{
+        $crate::_register_embedded!($filename);
+        $crate::LazyResource::<[u8]>::_from_data(include_bytes!($crate::_checked_path!($filename)))
+    }};
+}
+
+/// Open a binary resource as a seekable reader, reading embedded data in
+/// release mode, or streaming the file in debug.
+///
+/// Expands to an `io::Result<ResourceReader>`. In debug mode, the file is
+/// opened but not read up front, so large files can be streamed. In release
+/// mode, the data is embedded and the result is always `Ok`.
+///
+/// The filename is relative to the root of your crate, to `OUT_DIR` when
+/// prefixed with `out_dir:`, or to another crate in your workspace when
+/// prefixed with `package name,`.
+///
+/// # Errors
+///
+/// When dynamically including, this returns an error if the file can't be
+/// opened. Fallbacks set with `set_fallback` aren't used. When statically
+/// including, a missing file is a compile error.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::{Read, Seek, SeekFrom};
+///
+/// use resource::resource_reader;
+///
+/// let mut reader = resource_reader!("Cargo.toml").unwrap();
+/// reader.seek(SeekFrom::Start(1)).unwrap();
+///
+/// let mut name = [0; 7];
+/// reader.read_exact(&mut name).unwrap();
+/// assert_eq!(&name, b"package");
+/// ```
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[macro_export]
+macro_rules! resource_reader {
+    (package $package:ident, $filename:tt) => {
+        $crate::ResourceReader::open($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt) => {
+        $crate::ResourceReader::open($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt) => {
+        $crate::ResourceReader::open($crate::_checked_path!($filename))
+    };
+}
+
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[macro_export]
+macro_rules! resource_reader {
+    (package $package:ident, $filename:tt) => {{
+        $crate::_register_embedded!(package $package, $filename);
+        ::std::io::Result::Ok($crate::ResourceReader::from_bytes(include_bytes!($crate::_checked_path!(package $package, $filename))))
+    }};
+
+    (out_dir: $filename:tt) => {{
+        $crate::_register_embedded!(out_dir: $filename);
+        ::std::io::Result::Ok($crate::ResourceReader::from_bytes(include_bytes!($crate::_checked_path!(out_dir: $filename))))
+    }};
+
+    ($filename:tt) => {{
+        $crate::_register_embedded!($filename);
+        ::std::io::Result::Ok($crate::ResourceReader::from_bytes(include_bytes!($crate::_checked_path!($filename))))
+    }};
+}
+
+/// Load a resource in the background, returning a future which works with
+/// any executor.
+///
+/// In debug mode, the file is read on a small pool of threads owned by this
+/// crate, so loading doesn't block the thread evaluating the macro. In release
+/// mode, the data is embedded and the future resolves immediately.
+///
+/// The filename is relative to the root of your crate, to `OUT_DIR` when
+/// prefixed with `out_dir:`, or to another crate in your workspace when
+/// prefixed with `package name,`. Resources are binary unless a type is given
+/// with `as`, such as `as str`.
+///
+/// # Panics
+///
+/// When dynamically including, awaiting the future will panic if the file
+/// does not exist, unless a fallback has been set with `set_fallback`. When
+/// statically including, this will be a compile error and will never panic.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::resource_async;
+///
+/// async fn load_level() {
+///     let (map, script) = (
+///         resource_async!("tests/bytes.bin"),
+///         resource_async!("tests/str.txt" as str),
+///     );
+///
+///     let map = map.await;
+///     let script = script.await;
+/// }
+/// ```
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[macro_export]
+macro_rules! resource_async {
+    (package $package:ident, $filename:tt as $ty:ty) => {
+        $crate::LoadFuture::<$crate::Resource<$ty>>::_from_file($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt as $ty:ty) => {
+        $crate::LoadFuture::<$crate::Resource<$ty>>::_from_file($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt as $ty:ty) => {
+        $crate::LoadFuture::<$crate::Resource<$ty>>::_from_file($crate::_checked_path!($filename))
+    };
+
+    (package $package:ident, $filename:tt) => {
+        $crate::LoadFuture::<$crate::Resource<[u8]>>::_from_file($crate::_checked_path!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt) => {
+        $crate::LoadFuture::<$crate::Resource<[u8]>>::_from_file($crate::_checked_path!(out_dir: $filename))
+    };
+
+    ($filename:tt) => {
+        $crate::LoadFuture::<$crate::Resource<[u8]>>::_from_file($crate::_checked_path!($filename))
+    };
+}
+
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[macro_export]
+macro_rules! resource_async {
+    (package $package:ident, $filename:tt as $ty:ty) => {
+        $crate::LoadFuture::_ready($crate::resource!(package $package, $filename as $ty))
+    };
+
+    (out_dir: $filename:tt as $ty:ty) => {
+        $crate::LoadFuture::_ready($crate::resource!(out_dir: $filename as $ty))
+    };
+
+    ($filename:tt as $ty:ty) => {
+        $crate::LoadFuture::_ready($crate::resource!($filename as $ty))
+    };
+
+    (package $package:ident, $filename:tt) => {
+        $crate::LoadFuture::_ready($crate::resource!(package $package, $filename))
+    };
+
+    (out_dir: $filename:tt) => {
+        $crate::LoadFuture::_ready($crate::resource!(out_dir: $filename))
+    };
+
+    ($filename:tt) => {
+        $crate::LoadFuture::_ready($crate::resource!($filename))
+    };
+}
+
+#[cfg(test)]
+mod single_file_transform_tests {
+    fn rev_string(string: &str) -> String {
+        string.chars().rev().collect()
+    }
+
+    fn rev_bytes(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().cloned().rev().collect()
+    }
+
+    #[test]
+    fn transform_single_string() {
+        let s = resource_str!("tests/str.txt", rev_string);
+        assert_eq!(s, "\ngnirts\na\nsi\nsihT");
+    }
+
+    #[test]
+    fn transform_single_bytes() {
+        let s = resource!("tests/bytes.bin", rev_bytes);
+        assert_eq!(s, &[52, 51, 50, 49, 48]);
+    }
+}
+
+#[cfg(test)]
+mod other_crate_tests {
+    #[test]
+    fn load_from_other_crate() {
+        let readme = resource_str!(package resource_list_proc_macro, "README.md");
+        assert!(readme.contains("resource"));
+    }
+
+    #[test]
+    fn load_from_other_crate_with_fn() {
+        let lines = resource!(package resource_list_proc_macro, "Cargo.toml", |b: &[u8]| {
+            b.iter().filter(|&&b| b == b'\n').count()
+        });
+        assert!(lines > 0);
+    }
+}
+
+#[cfg(test)]
+mod multi_file_tests {
+    fn rev_string(string: &str) -> String {
+        string.chars().rev().collect()
+    }
+
+    fn rev_bytes(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().cloned().rev().collect()
+    }
+
+    #[test]
+    fn load_array_of_multiple_strings() {
+        let [a, b, c] = resource_str!([
+            "tests/string_a.txt",
+            "tests/string_b.txt",
+            "tests/string_c.txt",
+        ]);
+
+        assert_eq!([&*a, &*b, &*c], ["String A\n", "String B\n", "String C\n"]);
+    }
+
+    #[test]
+    fn load_tuple_of_multiple_strings() {
+        let (a, b, c) = resource_str!((
+            "tests/string_a.txt",
+            "tests/string_b.txt",
+            "tests/string_c.txt",
+        ));
+
+        assert_eq!(&*a, "String A\n");
+        assert_eq!(&*b, "String B\n");
+        assert_eq!(&*c, "String C\n");
+    }
+
+    #[test]
+    fn load_array_of_multiple_bytes() {
+        let [a, b, c] = resource!([
+            "tests/bytes_a.bin",
+            "tests/bytes_b.bin",
+            "tests/bytes_c.bin",
+        ]);
+
+        assert_eq!(
+            [&*a, &*b, &*c],
+            [
+                b"Bytes A".as_ref(),
+                b"Bytes B".as_ref(),
+                b"Bytes C".as_ref()
+            ]
+        );
+    }
+
+    #[test]
+    fn load_tuple_of_multiple_bytes() {
+        let (a, b, c) = resource!((
+            "tests/bytes_a.bin",
+            "tests/bytes_b.bin",
+            "tests/bytes_c.bin",
+        ));
+
+        assert_eq!(&*a, b"Bytes A".as_ref());
+        assert_eq!(&*b, b"Bytes B".as_ref());
+        assert_eq!(&*c, b"Bytes C".as_ref());
+    }
+
+    #[test]
+    fn load_with_fn_array_of_multiple_strings() {
+        let [a, b, c] = resource_str!(
+            [
+                "tests/string_a.txt",
+                "tests/string_b.txt",
+                "tests/string_c.txt",
+            ],
+            rev_string
+        );
+
+        assert_eq!([a, b, c], ["\nA gnirtS", "\nB gnirtS", "\nC gnirtS"]);
+    }
+
+    #[test]
+    fn load_with_fn_tuple_of_multiple_strings() {
+        let (a, b, c) = resource_str!(
+            (
+                "tests/string_a.txt",
+                "tests/string_b.txt",
+                "tests/string_c.txt",
+            ),
+            rev_string
+        );
+
+        assert_eq!(a, "\nA gnirtS");
+        assert_eq!(b, "\nB gnirtS");
+        assert_eq!(c, "\nC gnirtS");
+    }
+
+    #[test]
+    fn load_with_fn_array_of_multiple_bytes() {
+        let [a, b, c] = resource!(
+            [
+                "tests/bytes_a.bin",
+                "tests/bytes_b.bin",
+                "tests/bytes_c.bin",
+            ],
+            rev_bytes
+        );
+
+        assert_eq!(
+            [a, b, c],
+            [
+                b"A setyB".as_ref(),
+                b"B setyB".as_ref(),
+                b"C setyB".as_ref()
+            ]
+        );
+    }
+
+    #[test]
+    fn load_with_fn_tuple_of_multiple_bytes() {
+        let (a, b, c) = resource!(
+            (
+                "tests/bytes_a.bin",
+                "tests/bytes_b.bin",
+                "tests/bytes_c.bin",
+            ),
+            rev_bytes
+        );
+
+        assert_eq!(a, b"A setyB".as_ref());
+        assert_eq!(b, b"B setyB".as_ref());
+        assert_eq!(c, b"C setyB".as_ref());
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+#[allow(unused_imports)]
+mod dynamic_tests {
+    use super::*;
+
+    use std::borrow::Cow;
+
+    #[test]
+    fn str_dynamic() {
+        match resource_str!("tests/str.txt").into() {
+            Cow::Owned(ref s) if s == "This\nis\na\nstring\n" => (),
+            _ => panic!("Expected owned string!"),
+        }
+    }
+
+    #[test]
+    fn bytes_dynamic() {
+        match resource!("tests/bytes.bin").into() {
+            Cow::Owned(ref s) if s == &[48, 49, 50, 51, 52] => (),
+            _ => panic!("Expected owned bytes!"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+#[allow(unused_imports, clippy::redundant_guards, clippy::op_ref)]
+mod static_tests {
+    use super::*;
+
+    use std::borrow::Cow;
+
+    #[test]
+    fn str_static() {
+        match resource_str!("tests/str.txt").into() {
+            Cow::Borrowed(s) if s == "This\nis\na\nstring\n" => (),
+            _ => panic!("Expected borrowed string!"),
+        }
+    }
+
+    #[test]
+    fn bytes_static() {
+        match resource!("tests/bytes.bin").into() {
+            Cow::Borrowed(s) if s == &[48, 49, 50, 51, 52] => (),
+            _ => panic!("Expected borrowed bytes!"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(
+    feature = "force-static",
+    all(not(feature = "force-dynamic"), not(debug_assertions))
+))]
+mod static_reload_tests {
+    use crate::LazyResource;
+
+    #[test]
+    fn changed() {
+        std::fs::write("tests/temp/static_changed.txt", "Old").unwrap();
+
+        let res = resource_str!("tests/temp/static_changed.txt");
+        assert!(!res.changed());
+
+        std::fs::write("tests/temp/static_changed.txt", "New").unwrap();
+        let changed = res.changed();
+
+        // Revert
+        std::fs::write("tests/temp/static_changed.txt", "Old").unwrap();
+
+        assert!(!changed);
+    }
+
+    #[test]
+    fn reload() {
+        std::fs::write("tests/temp/static_reload.txt", "Old").unwrap();
+
+        let mut res = resource_str!("tests/temp/static_reload.txt");
+        assert_eq!(res.as_ref(), "Old");
+
+        std::fs::write("tests/temp/static_reload.txt", "New").unwrap();
+        res.reload();
+
+        // Revert
+        std::fs::write("tests/temp/static_reload.txt", "Old").unwrap();
+
+        assert_eq!(res.as_ref(), "Old");
+    }
+
+    #[test]
+    fn reload_if_changed() {
+        std::fs::write("tests/temp/static_reload_if_changed.txt", "Old").unwrap();
+
+        let mut res = resource_str!("tests/temp/static_reload_if_changed.txt");
+        assert!(!res.reload_if_changed().is_reloaded());
+        assert_eq!(res.as_ref(), "Old");
+
+        std::fs::write("tests/temp/static_reload_if_changed.txt", "New").unwrap();
+        let changed = res.reload_if_changed().is_reloaded();
+
+        // Revert
+        std::fs::write("tests/temp/static_reload_if_changed.txt", "Old").unwrap();
+
+        assert!(!changed);
+        assert_eq!(res.as_ref(), "Old");
+    }
+
+    #[test]
+    fn lazy_reload() {
+        static RES: LazyResource<str> = resource_str_static!("tests/temp/static_lazy_reload.txt");
+
+        std::fs::write("tests/temp/static_lazy_reload.txt", "New").unwrap();
+        let changed = RES.reload_if_changed().is_reloaded();
+
+        // Revert
+        std::fs::write("tests/temp/static_lazy_reload.txt", "Old").unwrap();
+
+        assert!(!changed);
+        assert_eq!(&*RES.get(), "Old");
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(
+    feature = "force-dynamic",
+    all(not(feature = "force-static"), debug_assertions)
+))]
+mod dynamic_reload_tests {
+    use crate::{LazyResource, ReloadError, ReloadOutcome};
+
+    #[test]
+    fn changed() {
+        // For some reason, `changed()` doesn't seem to work on Travis.
+        if option_env!("TRAVIS").is_none() {
+            std::fs::write("tests/temp/dynamic_changed.txt", "Old").unwrap();
+
+            let res = resource_str!("tests/temp/dynamic_changed.txt");
+            assert!(!res.changed());
+
+            std::fs::write("tests/temp/dynamic_changed.txt", "New").unwrap();
+            assert!(res.changed());
+        }
+    }
+
+    #[test]
+    fn reload() {
+        std::fs::write("tests/temp/dynamic_reload.txt", "Old").unwrap();
+
+        let mut res = resource_str!("tests/temp/dynamic_reload.txt");
+        std::fs::write("tests/temp/dynamic_reload.txt", "New").unwrap();
+
+        assert_eq!(res.as_ref(), "Old");
+        res.reload();
+        assert_eq!(res.as_ref(), "New");
+    }
+
+    #[test]
+    fn reload_if_changed() {
+        // For some reason, `changed()` doesn't seem to work on Travis.
+        if option_env!("TRAVIS").is_none() {
+            std::fs::write("tests/temp/dynamic_reload_if_changed.txt", "Old").unwrap();
+
+            let mut res = resource_str!("tests/temp/dynamic_reload_if_changed.txt");
+            assert!(!res.reload_if_changed().is_reloaded());
+            assert_eq!(res.as_ref(), "Old");
+
+            std::fs::write("tests/temp/dynamic_reload_if_changed.txt", "New").unwrap();
+            assert!(!res.reload_if_changed().is_reloaded());
+            assert_eq!(res.as_ref(), "Old");
+
+            std::thread::sleep(std::time::Duration::from_millis(150));
+            assert!(res.reload_if_changed().is_reloaded());
+            assert_eq!(res.as_ref(), "New");
+        }
+    }
+
+    #[test]
+    fn reload_missing_file_keeps_data() {
+        std::fs::write("tests/temp/dynamic_reload_missing.txt", "Old").unwrap();
+
+        let mut res = resource_str!("tests/temp/dynamic_reload_missing.txt");
+        std::fs::remove_file("tests/temp/dynamic_reload_missing.txt").unwrap();
+
+        let reloaded = res.reload();
+        let reloaded_if_changed = res.reload_if_changed();
+
+        // Revert
+        std::fs::write("tests/temp/dynamic_reload_missing.txt", "Old").unwrap();
+
+        assert!(matches!(
+            reloaded,
+            ReloadOutcome::Failed(ReloadError::Io(_))
+        ));
+        assert!(matches!(reloaded_if_changed, ReloadOutcome::Unchanged));
+        assert_eq!(res.as_ref(), "Old");
+    }
+
+    #[test]
+    fn reload_after_rename() {
+        std::fs::write("tests/temp/dynamic_reload_rename.txt", "Old").unwrap();
+
+        let mut res = resource_str!("tests/temp/dynamic_reload_rename.txt");
+        std::fs::remove_file("tests/temp/dynamic_reload_rename.txt").unwrap();
+
+        let writer = std::thread::spawn(|| {
+            std::thread::sleep(std::time::Duration::from_millis(30));
+            std::fs::write("tests/temp/dynamic_reload_rename.tmp", "New").unwrap();
+            std::fs::rename(
+                "tests/temp/dynamic_reload_rename.tmp",
+                "tests/temp/dynamic_reload_rename.txt",
+            )
+            .unwrap();
+        });
+
+        assert!(res.reload().is_reloaded());
+        writer.join().unwrap();
+
+        assert_eq!(res.as_ref(), "New");
+    }
+
+    #[test]
+    fn missing_file_uses_fallback() {
+        use crate::ReadFromFile;
+
+        // Fallbacks are global, so use a type of its own to avoid affecting
+        // other tests of missing files
+        #[derive(Clone, Debug, PartialEq)]
+        struct Text(String);
+
+        impl ReadFromFile for Text {
+            fn from_bytes(bytes: &[u8]) -> std::io::Result<Text> {
+                String::from_bytes(bytes).map(Text)
+            }
+        }
+
+        crate::set_fallback::<Text>(crate::Fallback::Value(|path| {
+            Text(format!("Missing {}", path.file_name().unwrap().display()))
+        }));
+
+        let dir = std::env::temp_dir().join(format!("resource_fallback_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("dynamic_fallback.txt");
+
+        let mut res = crate::Resource::<Text>::_from_file(path.to_str().unwrap());
+        assert_eq!(*res, Text("Missing dynamic_fallback.txt".to_owned()));
+        assert!(!res.changed());
+
+        std::fs::write(&path, "Found").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(150));
+        let reloaded = res.reload_if_changed();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(reloaded.is_reloaded());
+        assert_eq!(*res, Text("Found".to_owned()));
+    }
+
+    #[test]
+    fn missing_file_uses_placeholder() {
+        use crate::{Placeholder, ReadFromFile};
+
+        // Fallbacks are global, so use a type of its own to avoid affecting
+        // other tests of missing files
+        #[derive(Clone)]
+        struct Image(Vec<u8>);
+
+        impl ReadFromFile for Image {
+            fn from_bytes(bytes: &[u8]) -> std::io::Result<Image> {
+                Ok(Image(bytes.to_vec()))
+            }
+        }
+
+        impl Placeholder for Image {
+            fn placeholder() -> Image {
+                Image(Vec::placeholder())
+            }
+        }
+
+        crate::set_fallback::<Image>(crate::Fallback::placeholder());
+
+        let res = crate::Resource::<Image>::_from_file("tests/missing_placeholder.bin");
+        assert_eq!(&res.0[0..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn transformed_reload_keeps_last_good_value() {
+        fn parse_number(s: &str) -> Result<u32, std::num::ParseIntError> {
+            s.trim().parse()
+        }
+
+        std::fs::write("tests/temp/dynamic_transformed_reload.txt", "1").unwrap();
+
+        let mut number = resource_str!("tests/temp/dynamic_transformed_reload.txt")
+            .transform(parse_number)
+            .unwrap();
+
+        std::fs::write("tests/temp/dynamic_transformed_reload.txt", "Not a number").unwrap();
+        let outcome = number.reload();
+
+        assert!(matches!(
+            outcome,
+            ReloadOutcome::Failed(ReloadError::Transform(_))
+        ));
+        assert_eq!(*number, 1);
+
+        std::fs::write("tests/temp/dynamic_transformed_reload.txt", "2").unwrap();
+        assert!(number.reload().is_reloaded());
+        assert_eq!(*number, 2);
+    }
+
+    #[test]
+    fn lazy_reload() {
+        static RES: LazyResource<str> = resource_str_static!("tests/temp/dynamic_lazy_reload.txt");
+
+        std::fs::write("tests/temp/dynamic_lazy_reload.txt", "Old").unwrap();
+        assert_eq!(&*RES.get(), "Old");
+
+        std::fs::write("tests/temp/dynamic_lazy_reload.txt", "New").unwrap();
+        assert_eq!(&*RES.get(), "Old");
+
+        RES.reload();
+        assert_eq!(&*RES.get(), "New");
+    }
+}
+
+#[cfg(test)]
+mod transformed_tests {
+    fn parse_number(s: &str) -> Result<u32, std::num::ParseIntError> {
+        s.trim().parse()
+    }
+
+    #[test]
+    fn transform_str() {
+        let number = resource_str!("tests/number.txt")
+            .transform(parse_number)
+            .unwrap();
+        assert_eq!(*number, 42);
+    }
+
+    #[test]
+    fn transform_str_error() {
+        let result = resource_str!("tests/str.txt").transform(parse_number);
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod lazy_resource_tests {
+    use super::*;
+
+    static STR: LazyResource<str> = resource_str_static!("tests/str.txt");
+    static BYTES: LazyResource<[u8]> = resource_static!("tests/bytes.bin");
+    static STRINGS: [LazyResource<str>; 3] = resource_str_static!([
+        "tests/string_a.txt",
+        "tests/string_b.txt",
+        "tests/string_c.txt",
+    ]);
+
+    #[test]
+    fn lazy_str() {
+        assert_eq!(&*STR.get(), "This\nis\na\nstring\n");
+    }
+
+    #[test]
+    fn lazy_bytes() {
+        assert_eq!(&*BYTES.get(), &[48, 49, 50, 51, 52]);
+    }
+
+    #[test]
+    fn lazy_array_of_strings() {
+        let [a, b, c] = &STRINGS;
+        assert_eq!(
+            [&*a.get(), &*b.get(), &*c.get()],
+            ["String A\n", "String B\n", "String C\n"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod resource_list_tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_str_list() {
+        let files = resource_str_list!("tests/resource_list_test_files");
+        let names = ["file_a.txt", "file_b.txt", "file_c.txt"];
+        let contents = ["A\n", "B\n", "C\n"];
+        for i in 0..files.len() {
+            assert_eq!(files[i].0, names[i]);
+            assert_eq!(files[i].1.as_ref(), contents[i]);
+        }
+    }
+
+    #[test]
+    fn test_resource_str_list_raw_string() {
+        let files = resource_str_list!(r"tests/resource_list_test_files");
+        let names = ["file_a.txt", "file_b.txt", "file_c.txt"];
+        assert_eq!(files.len(), 3);
+        for i in 0..files.len() {
+            assert_eq!(files[i].0, names[i]);
+        }
+    }
+
+    #[test]
+    fn test_resource_list_concat() {
+        let files = resource_list!(concat!("tests/", "resource_list_test_files"),);
+        let names = ["file_a.txt", "file_b.txt", "file_c.txt"];
+        assert_eq!(files.len(), 3);
+        for i in 0..files.len() {
+            assert_eq!(files[i].0, names[i]);
+        }
+    }
+
+    #[test]
+    fn test_resource_str_list_relative_to_manifest() {
+        let files = resource_str_list!("tests/resource_list_test_files", relative_to = manifest);
+        let contents = ["A\n", "B\n", "C\n"];
+        assert_eq!(files.len(), 3);
+        for i in 0..files.len() {
+            assert_eq!(files[i].1.as_ref(), contents[i]);
+        }
+    }
+
+    #[test]
+    fn test_resource_list_relative_to_workspace() {
+        // This crate is the workspace root, so the paths are the same as
+        // relative to the manifest
+        let files = resource_list!("tests/resource_list_test_files", relative_to = workspace);
+        let names = ["file_a.txt", "file_b.txt", "file_c.txt"];
+        let contents = [b"A\n", b"B\n", b"C\n"];
+        assert_eq!(files.len(), 3);
+        for i in 0..files.len() {
+            assert_eq!(files[i].0, names[i]);
+            assert_eq!(files[i].1.as_ref(), contents[i]);
+        }
+    }
+
+    #[test]
+    fn test_resource_str_list_recursive() {
+        let files = resource_str_list!("tests/resource_list_nested_files", recursive = true);
+        let names = ["sub/inner.txt", "top.txt"];
+        let contents = ["Inner\n", "Top\n"];
+        assert_eq!(files.len(), 2);
+        for i in 0..files.len() {
+            assert_eq!(files[i].0, names[i]);
+            assert_eq!(files[i].1.as_ref(), contents[i]);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_resource_str_list_symlinks() {
+        let files = resource_str_list!("tests/resource_list_symlink_files");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "real.txt");
+
+        let files = resource_str_list!(
+            "tests/resource_list_symlink_files",
+            follow_symlinks = true,
+            recursive = true,
+        );
+        let names = ["link.txt", "linked_dir/inner.txt", "real.txt"];
+        let contents = ["B\n", "Inner\n", "Real\n"];
+        assert_eq!(files.len(), 3);
+        for i in 0..files.len() {
+            assert_eq!(files[i].0, names[i]);
+            assert_eq!(files[i].1.as_ref(), contents[i]);
+        }
+    }
+
+    #[test]
+    fn test_resource_list() {
+        let files = resource_list!("tests/resource_list_test_files");
+        let names = ["file_a.txt", "file_b.txt", "file_c.txt"];
+        let contents = [b"A\n", b"B\n", b"C\n"];
+        for i in 0..files.len() {
+            assert_eq!(files[i].0, names[i]);
+            assert_eq!(files[i].1.as_ref(), contents[i]);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "manifest")]
+mod manifest_tests {
+    #[test]
+    #[cfg(any(
+        feature = "force-static",
+        all(not(feature = "force-dynamic"), not(debug_assertions))
+    ))]
+    fn embedded_files_are_listed() {
+        use resource_common::fnv1a;
+
+        let number = resource_str!("tests/number.txt");
+
+        let manifest = crate::manifest();
+        let entry = manifest
+            .iter()
+            .find(|entry| entry.path == "tests/number.txt")
+            .expect("tests/number.txt is not in the manifest");
+
+        assert_eq!(entry.package, "resource");
+        assert_eq!(entry.size, number.len() as u64);
+        assert_eq!(entry.hash, fnv1a(number.as_bytes()));
+        assert!(entry.file.ends_with("lib.rs"));
+        assert!(crate::manifest_json().contains("\"path\": \"tests/number.txt\""));
+    }
+
+    #[test]
+    #[cfg(any(
+        feature = "force-dynamic",
+        all(not(feature = "force-static"), debug_assertions)
+    ))]
+    fn nothing_is_listed_when_dynamic() {
+        let _number = resource_str!("tests/number.txt");
+
+        assert!(crate::manifest().is_empty());
+        assert_eq!(crate::manifest_json(), "[\n]\n");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "build")]
+mod build_tests {
+    use crate::build::AssetManifest;
+
+    #[test]
+    #[cfg(unix)]
+    fn scan_lists_nested_and_linked_files() {
+        let assets = AssetManifest::new()
+            .dir("tests/resource_list_symlink_files")
+            .dir("./tests/resource_list_nested_files")
+            .scan()
+            .unwrap();
+
+        let paths: Vec<&str> = assets.iter().map(|asset| asset.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "tests/resource_list_nested_files/sub/inner.txt",
+                "tests/resource_list_nested_files/top.txt",
+                "tests/resource_list_symlink_files/link.txt",
+                "tests/resource_list_symlink_files/linked_dir/inner.txt",
+                "tests/resource_list_symlink_files/real.txt",
+            ]
+        );
+
+        let top = std::fs::read("tests/resource_list_nested_files/top.txt").unwrap();
+        assert_eq!(assets[1].size, top.len() as u64);
+        assert_eq!(assets[1].hash, resource_common::fnv1a(&top));
+    }
+
+    #[test]
+    fn scan_deduplicates_overlapping_dirs() {
+        let assets = AssetManifest::new()
+            .dir("tests/resource_list_nested_files")
+            .dir("tests/resource_list_nested_files/sub")
+            .scan()
+            .unwrap();
+
+        assert_eq!(assets.len(), 2);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn scan_rejects_symlink_cycles() {
+        let dir = std::env::temp_dir().join(format!("resource_asset_cycle_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("file.txt"), "File").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("loop")).unwrap();
+
+        let result = AssetManifest::new().dir(&dir).scan();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let error = result.unwrap_err();
+        assert!(error.to_string().contains("Symlink cycle"), "{}", error);
+    }
+}
+
+#[cfg(test)]
+mod open_tests {
+    use std::borrow::Cow;
+
+    use crate::{ReloadError, ReloadOutcome, Resource};
+
+    #[test]
+    fn open() {
+        let res = Resource::<str>::open("tests/str.txt").unwrap();
+        assert_eq!(res.as_ref(), "This\nis\na\nstring\n");
+
+        let res = Resource::<[u8]>::open("tests/bytes.bin").unwrap();
+        assert_eq!(res.as_ref(), [48, 49, 50, 51, 52]);
+    }
+
+    #[test]
+    fn open_missing_file() {
+        let start = std::time::Instant::now();
+        let error = Resource::<str>::open("tests/missing.txt").err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+
+        // Only reloads wait for a missing file to reappear
+        assert!(start.elapsed() < std::time::Duration::from_millis(50));
+    }
+
+    #[test]
+    fn reload_opened() {
+        std::fs::write("tests/temp/open_reload.txt", "Old").unwrap();
+
+        let mut res = Resource::<str>::open("tests/temp/open_reload.txt").unwrap();
+        assert!(!res.changed());
+
+        std::fs::write("tests/temp/open_reload.txt", "New").unwrap();
+        let reloaded = res.reload();
+
+        // Revert
+        std::fs::write("tests/temp/open_reload.txt", "Old").unwrap();
+
+        assert!(reloaded.is_reloaded());
+        assert_eq!(res.as_ref(), "New");
+    }
+
+    #[test]
+    fn reload_opened_missing_file_keeps_data() {
+        std::fs::write("tests/temp/open_reload_missing.txt", "Old").unwrap();
+
+        let mut res = Resource::<str>::open("tests/temp/open_reload_missing.txt").unwrap();
+        std::fs::remove_file("tests/temp/open_reload_missing.txt").unwrap();
+        let reloaded = res.reload();
+
+        // Revert
+        std::fs::write("tests/temp/open_reload_missing.txt", "Old").unwrap();
 
-    ([ $($filenames:tt),* $(,)* ]) => {
-        [ $(resource_str!($filenames)),* ]
-    };
+        assert!(matches!(
+            reloaded,
+            ReloadOutcome::Failed(ReloadError::Io(_))
+        ));
+        assert_eq!(res.as_ref(), "Old");
+    }
 
-    (( $($filenames:tt),* $(,)* )) => {
-        ( $(resource_str!($filenames)),* )
-    };
+    #[test]
+    fn from_static() {
+        let mut res = Resource::<str>::from_static("Static");
 
-    ($filename:tt, $load_fn:expr) => {
-        $load_fn(
-            <$crate::Resource<str> as std::convert::AsRef<str>>::as_ref(&resource_str!($filename))
-        )
-    };
+        assert!(!res.changed());
+        assert!(matches!(res.reload(), ReloadOutcome::Unchanged));
+        assert!(matches!(res.reload_if_changed(), ReloadOutcome::Unchanged));
 
-    ($filename:tt) => {
-        $crate::Resource::<str>::_from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/", $filename))
-    };
+        match res.into() {
+            Cow::Borrowed("Static") => (),
+            _ => panic!("Expected borrowed string!"),
+        }
+    }
 }
 
-#[cfg(any(
-    feature = "force-static",
-    all(not(feature = "force-dynamic"), not(debug_assertions))
-))]
-#[macro_export]
-macro_rules! resource_str {
-    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
-        [ $(resource_str!($filenames, $load_fn)),* ]
-    };
+#[cfg(test)]
+mod introspection_tests {
+    use crate::Resource;
 
-    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
-        ( $(resource_str!($filenames, $load_fn)),* )
-    };
+    #[test]
+    fn embedded() {
+        let res = resource_str!("tests/str.txt");
 
-    ([ $($filenames:tt),* $(,)* ]) => {
-        [ $(resource_str!($filenames)),* ]
-    };
+        assert!(res.path().unwrap().ends_with("tests/str.txt"));
+        assert_eq!(res.len(), 17);
+        assert!(!res.is_empty());
+        assert_eq!(res.generation(), 0);
+    }
 
-    (( $($filenames:tt),* $(,)* )) => {
-        ( $(resource_str!($filenames)),* )
-    };
+    #[test]
+    #[cfg(any(
+        feature = "force-static",
+        all(not(feature = "force-dynamic"), not(debug_assertions))
+    ))]
+    fn embedded_paths_are_relative_to_the_crate() {
+        let res = resource_str!("tests/str.txt");
+        assert_eq!(res.path().unwrap(), "tests/str.txt");
+
+        let res = resource!(package resource_list_proc_macro, "tests/string.txt");
+        assert_eq!(
+            res.path().unwrap(),
+            "resource_list_proc_macro/tests/string.txt"
+        );
+    }
 
-    ($filename:tt, $load_fn:expr) => {
-        $load_fn(
-            <$crate::Resource<str> as std::convert::AsRef<str>>::as_ref(&resource_str!($filename))
-        )
-    };
+    #[test]
+    #[cfg(any(
+        feature = "force-dynamic",
+        all(not(feature = "force-static"), debug_assertions)
+    ))]
+    fn embedded_timestamps_when_dynamic() {
+        let res = resource!("tests/bytes.bin");
+
+        assert!(res.loaded_at().is_some());
+        assert!(res.modified().is_some());
+    }
 
-    ($filename:tt) => {
-        $crate::Resource::<str>::_from_data(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $filename)))
-    };
+    #[test]
+    #[cfg(any(
+        feature = "force-static",
+        all(not(feature = "force-dynamic"), not(debug_assertions))
+    ))]
+    fn embedded_timestamps_when_static() {
+        let res = resource!("tests/bytes.bin");
+
+        assert_eq!(res.loaded_at(), None);
+        assert_eq!(res.modified(), None);
+    }
+
+    #[test]
+    fn from_static() {
+        let res = Resource::<[u8]>::from_static(b"");
+
+        assert_eq!(res.path(), None);
+        assert!(res.is_empty());
+        assert_eq!(res.loaded_at(), None);
+        assert_eq!(res.modified(), None);
+        assert_eq!(res.generation(), 0);
+    }
+
+    #[test]
+    fn reload_bumps_generation() {
+        let mut res = Resource::<str>::open("tests/number.txt").unwrap();
+        let loaded_at = res.loaded_at().unwrap();
+
+        assert_eq!(res.path().unwrap(), "tests/number.txt");
+        assert_eq!(res.len(), 3);
+        assert_eq!(res.generation(), 0);
+
+        assert!(res.reload().is_reloaded());
+        assert!(res.reload().is_reloaded());
+        assert_eq!(res.generation(), 2);
+        assert!(res.loaded_at().unwrap() >= loaded_at);
+    }
 }
 
-/// Load binary resources statically in release mode, or dynamically in
-/// debug.
-///
-/// The filenames are relative to the root of your crate.
-///
-/// If you wish to override the static or dynamic behaviour, you can use the
-/// `force-static` or `force-dynamic` features.
-///
-/// This macro optionally takes a function which can be used to transform the
-/// contents of each file on load.
-///
-/// # Panics
-///
-/// When dynamically including, this will panic if any file does not exist. When
-/// statically including, this will be a compile error and will never panic.
-///
-/// # Examples
-///
-/// Load a single binary file:
-///
-/// ```rust
-/// use resource::resource;
-///
-/// let toml = resource!("Cargo.toml");
-/// assert_eq!(&toml[0..9], b"[package]");
-/// ```
-///
-/// Load an array or tuple of binary files:
-///
-/// ```rust
-/// use resource::resource;
-///
-/// let (toml, lib) = resource!(("Cargo.toml", "src/lib.rs"));
-/// let as_array = resource!(["Cargo.toml", "src/lib.rs"]);
-///
-/// assert_eq!(toml.as_ref(), as_array[0].as_ref());
-/// assert_eq!(lib.as_ref(), as_array[1].as_ref());
-/// ```
-///
-/// Load binary files and apply a transformation to each one:
-///
-/// ```rust
-/// use resource::resource;
-///
-/// let [toml, lib] = resource!(["Cargo.toml", "src/lib.rs"],
-///     |bytes: &[u8]| bytes.to_ascii_uppercase());
-///
-/// assert_eq!(&toml[0..9], b"[PACKAGE]");
-/// assert_eq!(&lib[0..4], b"//! ");
-/// ```
-#[cfg(any(
-    feature = "force-dynamic",
-    all(not(feature = "force-static"), debug_assertions)
-))]
-#[macro_export]
-macro_rules! resource {
-    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
-        [ $(resource!($filenames, $load_fn)),* ]
-    };
+#[cfg(test)]
+mod trait_tests {
+    use std::collections::HashMap;
 
-    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
-        ( $(resource!($filenames, $load_fn)),* )
-    };
+    use crate::Resource;
 
-    ([ $($filenames:tt),* $(,)* ]) => {
-        [ $(resource!($filenames)),* ]
-    };
+    #[test]
+    fn compare_by_content() {
+        let loaded = resource_str!("tests/number.txt");
+        let embedded = Resource::<str>::from_static("42\n");
 
-    (( $($filenames:tt),* $(,)* )) => {
-        ( $(resource!($filenames)),* )
-    };
+        assert_eq!(loaded, embedded);
+        assert!(Resource::<str>::from_static("1") < Resource::<str>::from_static("2"));
+    }
 
-    ($filename:tt, $load_fn:expr) => {
-        $load_fn(
-            <$crate::Resource<[u8]> as std::convert::AsRef<[u8]>>::as_ref(&resource!($filename))
-        )
-    };
+    #[test]
+    fn hash_map_key() {
+        let mut map = HashMap::new();
+        map.insert(resource_str!("tests/number.txt"), "number");
 
-    ($filename:tt) => {
-        $crate::Resource::<[u8]>::_from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/", $filename))
-    };
+        assert_eq!(map.get("42\n"), Some(&"number"));
+    }
+
+    #[test]
+    fn format() {
+        let res = Resource::<str>::from_static("Text");
+
+        assert_eq!(res.to_string(), "Text");
+        assert_eq!(format!("{:?}", res), r#"Resource { path: None, len: 4 }"#);
+    }
 }
 
-#[cfg(any(
-    feature = "force-static",
-    all(not(feature = "force-dynamic"), not(debug_assertions))
-))]
-#[macro_export]
-macro_rules! resource {
-    ([ $($filenames:tt),* $(,)* ], $load_fn:expr) => {
-        [ $(resource!($filenames, $load_fn)),* ]
-    };
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod serde_tests {
+    use crate::Resource;
 
-    (( $($filenames:tt),* $(,)* ), $load_fn:expr) => {
-        ( $(resource!($filenames, $load_fn)),* )
-    };
+    #[test]
+    fn serialize_content() {
+        let res = resource_str!("tests/number.txt");
+        assert_eq!(serde_json::to_string(&res).unwrap(), r#""42\n""#);
 
-    ([ $($filenames:tt),* $(,)* ]) => {
-        [ $(resource!($filenames)),* ]
-    };
+        let res: Resource<str> = serde_json::from_str(r#""Text""#).unwrap();
+        assert_eq!(res.as_ref(), "Text");
+        assert!(!res.changed());
+    }
 
-    (( $($filenames:tt),* $(,)* )) => {
-        ( $(resource!($filenames)),* )
-    };
+    #[test]
+    fn serialize_path() {
+        let res = Resource::<str>::open("tests/number.txt").unwrap();
+
+        let mut json = vec![];
+        crate::serde_path::serialize(&res, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(json, br#""tests/number.txt""#);
+
+        let res: Resource<str> =
+            crate::serde_path::deserialize(&mut serde_json::Deserializer::from_slice(&json))
+                .unwrap();
+        assert_eq!(res.as_ref(), "42\n");
+        assert_eq!(res.path().unwrap(), "tests/number.txt");
+    }
 
-    ($filename:tt, $load_fn:expr) => {
-        $load_fn(
-            <$crate::Resource<[u8]> as std::convert::AsRef<[u8]>>::as_ref(&resource!($filename))
-        )
-    };
+    #[test]
+    #[cfg(any(
+        feature = "force-static",
+        all(not(feature = "force-dynamic"), not(debug_assertions))
+    ))]
+    fn serialize_embedded_path() {
+        let res = resource_str!("tests/number.txt");
+
+        let mut json = vec![];
+        crate::serde_path::serialize(&res, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(json, br#""tests/number.txt""#);
+    }
 
-    ($filename:tt) => {
-        $crate::Resource::<[u8]>::_from_data(include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $filename)))
-    };
+    #[test]
+    fn serialize_path_without_file() {
+        let res = Resource::<str>::from_static("Text");
+        let mut json = vec![];
+
+        assert!(
+            crate::serde_path::serialize(&res, &mut serde_json::Serializer::new(&mut json))
+                .is_err()
+        );
+    }
 }
 
 #[cfg(test)]
-mod single_file_transform_tests {
-    fn rev_string(string: &str) -> String {
-        string.chars().rev().collect()
+mod custom_type_tests {
+    use std::io;
+
+    use crate::{ReadFromFile, ReloadError, ReloadOutcome, Resource};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Number(u32);
+
+    impl ReadFromFile for Number {
+        fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+            std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|text| text.trim().parse().ok())
+                .map(Number)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not a number"))
+        }
     }
 
-    fn rev_bytes(bytes: &[u8]) -> Vec<u8> {
-        bytes.iter().cloned().rev().collect()
+    #[test]
+    fn load_custom_type() {
+        let number = resource!("tests/number.txt" as Number);
+        assert_eq!(*number, Number(42));
+
+        let other = resource!(package resource_list_proc_macro, "tests/number.txt" as Number);
+        assert_eq!(*other, Number(7));
     }
 
     #[test]
-    fn transform_single_string() {
-        let s = resource_str!("tests/str.txt", rev_string);
-        assert_eq!(s, "\ngnirts\na\nsi\nsihT");
+    fn len_is_file_size() {
+        let number = resource!("tests/number.txt" as Number);
+        assert_eq!(number.len(), 3);
+        assert!(!number.is_empty());
     }
 
     #[test]
-    fn transform_single_bytes() {
-        let s = resource!("tests/bytes.bin", rev_bytes);
-        assert_eq!(s, &[52, 51, 50, 49, 48]);
+    fn reload_custom_type() {
+        std::fs::write("tests/temp/custom_reload.txt", "1").unwrap();
+
+        let mut number = Resource::<Number>::open("tests/temp/custom_reload.txt").unwrap();
+        std::fs::write("tests/temp/custom_reload.txt", "2").unwrap();
+        let reloaded = number.reload();
+        std::fs::write("tests/temp/custom_reload.txt", "Two").unwrap();
+        let failed = number.reload();
+
+        // Revert
+        std::fs::write("tests/temp/custom_reload.txt", "1").unwrap();
+
+        assert!(reloaded.is_reloaded());
+        assert!(matches!(
+            failed,
+            ReloadOutcome::Failed(ReloadError::Io(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+        assert_eq!(*number, Number(2));
     }
 }
 
 #[cfg(test)]
-mod multi_file_tests {
-    fn rev_string(string: &str) -> String {
-        string.chars().rev().collect()
+mod aligned_tests {
+    use crate::{AlignedBytes, CastError, Resource};
+
+    #[test]
+    fn aligned() {
+        let data = resource!("tests/aligned.bin", align = 64);
+
+        assert_eq!(data.as_ptr().align_offset(64), 0);
+        assert_eq!(data.as_slice::<u64>().unwrap(), [0x0101_0101_0101_0101; 2]);
+        assert_eq!(data.as_slice::<[u8; 8]>().unwrap(), [[1; 8]; 2]);
+        assert_eq!(
+            data.as_slice::<[u8; 3]>().unwrap_err(),
+            CastError::LengthMismatch
+        );
+        assert_eq!(
+            data.as_slice::<[u8; 0]>().unwrap_err(),
+            CastError::ZeroSized
+        );
     }
 
-    fn rev_bytes(bytes: &[u8]) -> Vec<u8> {
-        bytes.iter().cloned().rev().collect()
+    #[test]
+    fn aligned_from_other_crate() {
+        let data = resource!(package resource_list_proc_macro, "tests/aligned.bin", align = 8);
+        assert_eq!(
+            data.as_slice::<u32>().unwrap(),
+            [
+                u32::from_le_bytes([1, 0, 0, 0]),
+                u32::from_le_bytes([2, 0, 0, 0])
+            ]
+        );
     }
 
     #[test]
-    fn load_array_of_multiple_strings() {
-        let [a, b, c] = resource_str!([
-            "tests/string_a.txt",
-            "tests/string_b.txt",
-            "tests/string_c.txt",
-        ]);
+    fn misaligned() {
+        let data = resource!("tests/aligned.bin", align = 16);
+        let misaligned = AlignedBytes::_new(&data[1..9]);
 
-        assert_eq!([&*a, &*b, &*c], ["String A\n", "String B\n", "String C\n"]);
+        assert_eq!(
+            misaligned.as_slice::<u64>().unwrap_err(),
+            CastError::Misaligned
+        );
     }
 
     #[test]
-    fn load_tuple_of_multiple_strings() {
-        let (a, b, c) = resource_str!((
-            "tests/string_a.txt",
-            "tests/string_b.txt",
-            "tests/string_c.txt",
-        ));
+    fn reload_stays_aligned() {
+        std::fs::write("tests/temp/aligned_reload.bin", [0; 4]).unwrap();
 
-        assert_eq!(&*a, "String A\n");
-        assert_eq!(&*b, "String B\n");
-        assert_eq!(&*c, "String C\n");
+        let mut data = Resource::<AlignedBytes>::open("tests/temp/aligned_reload.bin").unwrap();
+        std::fs::write("tests/temp/aligned_reload.bin", [1; 8]).unwrap();
+        let reloaded = data.reload();
+
+        // Revert
+        std::fs::write("tests/temp/aligned_reload.bin", [0; 4]).unwrap();
+
+        assert!(reloaded.is_reloaded());
+        assert_eq!(data.as_slice::<u64>().unwrap(), [0x0101_0101_0101_0101]);
     }
+}
+
+#[cfg(test)]
+mod reader_tests {
+    use std::io::{BufRead, Read, Seek, SeekFrom};
+
+    use crate::{Resource, ResourceReader};
 
     #[test]
-    fn load_array_of_multiple_bytes() {
-        let [a, b, c] = resource!([
-            "tests/bytes_a.bin",
-            "tests/bytes_b.bin",
-            "tests/bytes_c.bin",
-        ]);
+    fn resource_reader() {
+        let text = resource_str!("tests/str.txt");
+        let mut reader = text.reader();
 
-        assert_eq!(
-            [&*a, &*b, &*c],
-            [
-                b"Bytes A".as_ref(),
-                b"Bytes B".as_ref(),
-                b"Bytes C".as_ref()
-            ]
-        );
+        let lines: Vec<String> = (&mut reader).lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["This", "is", "a", "string"]);
+
+        reader.seek(SeekFrom::Start(5)).unwrap();
+        assert_eq!(reader.fill_buf().unwrap(), b"is\na\nstring\n");
+        assert!(!reader.is_file());
+    }
+
+    #[test]
+    fn reader_macro() {
+        let mut reader = resource_reader!("tests/bytes_a.bin").unwrap();
+
+        let end = reader.seek(SeekFrom::End(-1)).unwrap();
+        let mut last = [0];
+        reader.read_exact(&mut last).unwrap();
+
+        assert_eq!(end, 6);
+        assert_eq!(&last, b"A");
+    }
+
+    #[test]
+    fn reader_macro_from_other_crate() {
+        let mut reader =
+            resource_reader!(package resource_list_proc_macro, "tests/bytes.bin").unwrap();
+
+        let mut contents = vec![];
+        reader.read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, b"Macro bytes");
     }
 
+    #[cfg(any(
+        feature = "force-dynamic",
+        all(not(feature = "force-static"), debug_assertions)
+    ))]
     #[test]
-    fn load_tuple_of_multiple_bytes() {
-        let (a, b, c) = resource!((
-            "tests/bytes_a.bin",
-            "tests/bytes_b.bin",
-            "tests/bytes_c.bin",
-        ));
+    fn dynamic_reader_streams_file() {
+        assert!(resource_reader!("tests/str.txt").unwrap().is_file());
+    }
 
-        assert_eq!(&*a, b"Bytes A".as_ref());
-        assert_eq!(&*b, b"Bytes B".as_ref());
-        assert_eq!(&*c, b"Bytes C".as_ref());
+    #[test]
+    fn open_reader() {
+        let mut reader = ResourceReader::open("tests/string_b.txt").unwrap();
+
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "String B\n");
+        assert!(ResourceReader::open("tests/missing.txt").is_err());
     }
 
     #[test]
-    fn load_with_fn_array_of_multiple_strings() {
-        let [a, b, c] = resource_str!(
-            [
-                "tests/string_a.txt",
-                "tests/string_b.txt",
-                "tests/string_c.txt",
-            ],
-            rev_string
-        );
+    fn aligned_reader() {
+        let data = Resource::<crate::AlignedBytes>::open("tests/aligned.bin").unwrap();
+        assert_eq!(data.reader().seek(SeekFrom::End(0)).unwrap(), 16);
+    }
+}
 
-        assert_eq!([a, b, c], ["\nA gnirtS", "\nB gnirtS", "\nC gnirtS"]);
+#[cfg(test)]
+mod shared_tests {
+    use std::borrow::Cow;
+
+    use crate::Resource;
+
+    #[test]
+    fn clone_shares_data() {
+        let text = resource_str!("tests/str.txt");
+        let clone = text.clone();
+
+        assert_eq!(text.as_ptr(), clone.as_ptr());
     }
 
     #[test]
-    fn load_with_fn_tuple_of_multiple_strings() {
-        let (a, b, c) = resource_str!(
-            (
-                "tests/string_a.txt",
-                "tests/string_b.txt",
-                "tests/string_c.txt",
-            ),
-            rev_string
-        );
+    fn resource_is_small() {
+        // The file state is kept with the loaded data, not in every resource
+        assert!(size_of::<Resource<str>>() <= 3 * size_of::<usize>());
+        assert!(size_of::<Resource<[u8]>>() <= 3 * size_of::<usize>());
+    }
 
-        assert_eq!(a, "\nA gnirtS");
-        assert_eq!(b, "\nB gnirtS");
-        assert_eq!(c, "\nC gnirtS");
+    #[test]
+    fn into_cow_copies_only_when_shared() {
+        let bytes = Resource::<[u8]>::open("tests/bytes_a.bin").unwrap();
+        let clone = bytes.clone();
+
+        let cow: Cow<'static, [u8]> = bytes.into();
+        assert_ne!(cow.as_ptr(), clone.as_ptr());
+
+        let ptr = clone.as_ptr();
+        let cow: Cow<'static, [u8]> = clone.into();
+        assert_eq!(cow.as_ptr(), ptr);
     }
 
+    #[cfg(feature = "bytes")]
     #[test]
-    fn load_with_fn_array_of_multiple_bytes() {
-        let [a, b, c] = resource!(
-            [
-                "tests/bytes_a.bin",
-                "tests/bytes_b.bin",
-                "tests/bytes_c.bin",
-            ],
-            rev_bytes
-        );
+    fn to_bytes_without_copying() {
+        let embedded = resource!("tests/bytes_a.bin");
+        assert_eq!(embedded.to_bytes().as_ptr(), embedded.as_ptr());
 
-        assert_eq!(
-            [a, b, c],
-            [
-                b"A setyB".as_ref(),
-                b"B setyB".as_ref(),
-                b"C setyB".as_ref()
-            ]
-        );
+        let text = Resource::<str>::open("tests/str.txt").unwrap();
+        let bytes = bytes::Bytes::from(text.clone());
+        assert_eq!(bytes.as_ptr(), text.as_ptr());
+
+        drop(text);
+        assert_eq!(bytes, "This\nis\na\nstring\n");
     }
 
+    #[cfg(feature = "bytes")]
     #[test]
-    fn load_with_fn_tuple_of_multiple_bytes() {
-        let (a, b, c) = resource!(
-            (
-                "tests/bytes_a.bin",
-                "tests/bytes_b.bin",
-                "tests/bytes_c.bin",
-            ),
-            rev_bytes
-        );
+    fn bytes_outlive_reload() {
+        std::fs::write("tests/temp/shared_reload.bin", "Before").unwrap();
 
-        assert_eq!(a, b"A setyB".as_ref());
-        assert_eq!(b, b"B setyB".as_ref());
-        assert_eq!(c, b"C setyB".as_ref());
+        let mut data = Resource::<[u8]>::open("tests/temp/shared_reload.bin").unwrap();
+        let bytes = data.to_bytes();
+        std::fs::write("tests/temp/shared_reload.bin", "After").unwrap();
+        let reloaded = data.reload();
+
+        // Revert
+        std::fs::write("tests/temp/shared_reload.bin", "Before").unwrap();
+
+        assert!(reloaded.is_reloaded());
+        assert_eq!(&*data, b"After");
+        assert_eq!(bytes, "Before");
     }
 }
 
 #[cfg(test)]
-#[cfg(any(
-    feature = "force-dynamic",
-    all(not(feature = "force-static"), debug_assertions)
-))]
-mod dynamic_tests {
-    use super::*;
+mod leak_tests {
+    use std::io;
 
-    use std::borrow::Cow;
+    use crate::{ReadFromFile, Resource, leaked_bytes, set_leak_limit};
 
-    #[test]
-    fn str_dynamic() {
-        match resource_str!("tests/str.txt").into() {
-            Cow::Owned(ref s) if s == "This\nis\na\nstring\n" => (),
-            _ => panic!("Expected owned string!"),
+    // A type which is larger in memory than the file it is read from
+    #[derive(Clone)]
+    struct Length(usize);
+
+    impl ReadFromFile for Length {
+        fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+            Ok(Length(bytes.len()))
         }
     }
 
+    // The leak registry is global, so this is one test to keep the counts
+    // predictable
     #[test]
-    fn bytes_dynamic() {
-        match resource!("tests/bytes.bin").into() {
-            Cow::Owned(ref s) if s == &[48, 49, 50, 51, 52] => (),
-            _ => panic!("Expected owned bytes!"),
-        }
+    fn into_static() {
+        let embedded = Resource::<str>::from_static("Static");
+        assert!(std::ptr::eq(embedded.clone().into_static(), &*embedded));
+
+        std::fs::write("tests/temp/leak_reload.txt", "Before").unwrap();
+        let before = leaked_bytes();
+
+        let first = Resource::<str>::open("tests/temp/leak_reload.txt").unwrap();
+        let second = first.clone();
+        let first = first.into_static();
+        assert!(std::ptr::eq(first, second.into_static()));
+        assert_eq!(leaked_bytes(), before + 6);
+
+        // Make sure the modification time changes
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        std::fs::write("tests/temp/leak_reload.txt", "Changed").unwrap();
+        let changed = Resource::<str>::open("tests/temp/leak_reload.txt")
+            .unwrap()
+            .into_static();
+
+        // Only the bytes read from the file count, not the size of the type
+        let length = Resource::<Length>::open("tests/number.txt")
+            .unwrap()
+            .into_static();
+        assert_eq!(leaked_bytes(), before + 16);
+
+        set_leak_limit(leaked_bytes());
+        let over_limit = std::panic::catch_unwind(|| {
+            Resource::<str>::open("tests/str.txt")
+                .unwrap()
+                .into_static();
+        });
+        set_leak_limit(256 * 1024 * 1024);
+
+        // Revert
+        std::fs::write("tests/temp/leak_reload.txt", "Before").unwrap();
+
+        assert_eq!(first, "Before");
+        assert_eq!(changed, "Changed");
+        assert_eq!(length.0, 3);
+        assert_eq!(leaked_bytes(), before + 16);
+        assert!(over_limit.is_err());
     }
 }
 
 #[cfg(test)]
-#[cfg(any(
-    feature = "force-static",
-    all(not(feature = "force-dynamic"), not(debug_assertions))
-))]
-mod static_tests {
-    use super::*;
+mod async_tests {
+    use std::{
+        future::Future,
+        pin::pin,
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+        thread::Thread,
+    };
 
-    use std::borrow::Cow;
+    use crate::Resource;
 
-    #[test]
-    fn str_static() {
-        match resource_str!("tests/str.txt").into() {
-            Cow::Borrowed(s) if s == "This\nis\na\nstring\n" => (),
-            _ => panic!("Expected borrowed string!"),
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
         }
     }
 
-    #[test]
-    fn bytes_static() {
-        match resource!("tests/bytes.bin").into() {
-            Cow::Borrowed(s) if s == &[48, 49, 50, 51, 52] => (),
-            _ => panic!("Expected borrowed bytes!"),
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => std::thread::park(),
+            }
         }
     }
-}
 
-#[cfg(test)]
-#[cfg(any(
-    feature = "force-static",
-    all(not(feature = "force-dynamic"), not(debug_assertions))
-))]
-mod static_reload_tests {
+    #[test]
+    fn resource_async() {
+        let bytes = block_on(resource_async!("tests/bytes_a.bin"));
+        let text = block_on(resource_async!("tests/str.txt" as str));
+
+        assert_eq!(&*bytes, b"Bytes A");
+        assert_eq!(&*text, "This\nis\na\nstring\n");
+    }
 
     #[test]
-    fn changed() {
-        std::fs::write("tests/temp/static_changed.txt", "Old").unwrap();
+    fn resource_async_from_other_crate() {
+        let text =
+            block_on(resource_async!(package resource_list_proc_macro, "tests/string.txt" as str));
+        assert_eq!(&*text, "Macro string\n");
+    }
 
-        let res = resource_str!("tests/temp/static_changed.txt");
-        assert!(!res.changed());
+    #[test]
+    fn many_at_once() {
+        let futures: Vec<_> = (0..16)
+            .map(|_| resource_async!("tests/bytes_b.bin"))
+            .collect();
 
-        std::fs::write("tests/temp/static_changed.txt", "New").unwrap();
-        let changed = res.changed();
+        for future in futures {
+            assert_eq!(&*block_on(future), b"Bytes B");
+        }
+    }
+
+    #[test]
+    fn reload_async() {
+        std::fs::write("tests/temp/async_reload.txt", "Before").unwrap();
+
+        let mut text = Resource::<str>::open("tests/temp/async_reload.txt").unwrap();
+        std::fs::write("tests/temp/async_reload.txt", "After").unwrap();
+        let reloaded = block_on(text.reload_async());
+        let missing = {
+            let mut missing = text.clone();
+            std::fs::remove_file("tests/temp/async_reload.txt").unwrap();
+            block_on(missing.reload_async())
+        };
 
         // Revert
-        std::fs::write("tests/temp/static_changed.txt", "Old").unwrap();
+        std::fs::write("tests/temp/async_reload.txt", "Before").unwrap();
 
-        assert!(!changed);
+        assert!(reloaded.is_reloaded());
+        assert_eq!(&*text, "After");
+        assert_eq!(text.generation(), 1);
+        assert!(matches!(missing, crate::ReloadOutcome::Failed(_)));
     }
 
     #[test]
-    fn reload() {
-        std::fs::write("tests/temp/static_reload.txt", "Old").unwrap();
+    fn reload_async_static() {
+        let mut text = Resource::<str>::from_static("Static");
+        assert!(!block_on(text.reload_async()).is_reloaded());
+    }
+}
 
-        let mut res = resource_str!("tests/temp/static_reload.txt");
-        assert_eq!(res.as_ref(), "Old");
+#[cfg(test)]
+mod parallel_tests {
+    use crate::Resource;
 
-        std::fs::write("tests/temp/static_reload.txt", "New").unwrap();
-        res.reload();
+    #[test]
+    fn open_all_in_order() {
+        let paths: Vec<String> = (0..64)
+            .map(|i| {
+                [
+                    "tests/string_a.txt",
+                    "tests/string_b.txt",
+                    "tests/string_c.txt",
+                ][i % 3]
+            })
+            .map(str::to_owned)
+            .collect();
+        let resources = Resource::<str>::open_all(&paths).unwrap();
+
+        assert_eq!(resources.len(), 64);
+        for (path, resource) in paths.iter().zip(&resources) {
+            assert_eq!(resource.path().unwrap(), std::path::Path::new(path));
+        }
+        assert_eq!(&*resources[4], "String B\n");
+    }
 
-        // Revert
-        std::fs::write("tests/temp/static_reload.txt", "Old").unwrap();
+    #[test]
+    fn open_all_reports_every_error() {
+        let errors = Resource::<[u8]>::open_all(&[
+            "tests/missing_a.bin",
+            "tests/bytes_a.bin",
+            "tests/missing_b.bin",
+        ])
+        .unwrap_err();
 
-        assert_eq!(res.as_ref(), "Old");
+        let paths: Vec<_> = errors.errors().iter().map(|(path, _)| path).collect();
+        assert_eq!(paths, ["tests/missing_a.bin", "tests/missing_b.bin"]);
+        assert!(errors.to_string().starts_with("failed to read 2 files:"));
     }
 
     #[test]
-    fn reload_if_changed() {
-        std::fs::write("tests/temp/static_reload_if_changed.txt", "Old").unwrap();
+    fn array_in_order() {
+        let [a, b, c, a_again] = resource!([
+            "tests/bytes_a.bin",
+            "tests/bytes_b.bin",
+            "tests/bytes_c.bin",
+            "tests/bytes_a.bin",
+        ]);
 
-        let mut res = resource_str!("tests/temp/static_reload_if_changed.txt");
-        assert!(!res.reload_if_changed());
-        assert_eq!(res.as_ref(), "Old");
+        assert_eq!(&*a, b"Bytes A");
+        assert_eq!(&*b, b"Bytes B");
+        assert_eq!(&*c, b"Bytes C");
+        assert_eq!(a, a_again);
+    }
 
-        std::fs::write("tests/temp/static_reload_if_changed.txt", "New").unwrap();
-        let changed = res.reload_if_changed();
+    #[cfg(any(
+        feature = "force-dynamic",
+        all(not(feature = "force-static"), debug_assertions)
+    ))]
+    #[test]
+    fn aggregated_panic() {
+        let panic = std::panic::catch_unwind(|| {
+            Resource::<[u8]>::_from_files([
+                "tests/missing_a.bin",
+                "tests/bytes_a.bin",
+                "tests/missing_b.bin",
+            ])
+        })
+        .unwrap_err();
+
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.contains("tests/missing_a.bin"));
+        assert!(message.contains("tests/missing_b.bin"));
+    }
 
-        // Revert
-        std::fs::write("tests/temp/static_reload_if_changed.txt", "Old").unwrap();
+    #[test]
+    fn try_array_and_list() {
+        let [a, b] = resource!(try ["tests/bytes_a.bin", "tests/bytes_b.bin"]).unwrap();
+        assert_eq!(&*a, b"Bytes A");
+        assert_eq!(&*b, b"Bytes B");
+
+        let files =
+            crate::resource_str_list!("tests/resource_list_test_files", try = true).unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].0, "file_a.txt");
+        assert_eq!(&*files[0].1, "A\n");
+    }
 
-        assert!(!changed);
-        assert_eq!(res.as_ref(), "Old");
+    #[cfg(any(
+        feature = "force-dynamic",
+        all(not(feature = "force-static"), debug_assertions)
+    ))]
+    #[test]
+    fn try_reports_every_error() {
+        let errors = Resource::<[u8]>::_try_from_named_files([
+            ("missing_a.bin", "tests/missing_a.bin"),
+            ("bytes_a.bin", "tests/bytes_a.bin"),
+            ("missing_b.bin", "tests/missing_b.bin"),
+        ])
+        .unwrap_err();
+
+        let paths: Vec<_> = errors.errors().iter().map(|(path, _)| path).collect();
+        assert_eq!(paths, ["tests/missing_a.bin", "tests/missing_b.bin"]);
     }
 }
 
 #[cfg(test)]
-#[cfg(any(
-    feature = "force-dynamic",
-    all(not(feature = "force-static"), debug_assertions)
-))]
-mod dynamic_reload_tests {
+mod progress_tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use crate::{LoadProgress, Resource, resource_str_list};
 
     #[test]
-    fn changed() {
-        // For some reason, `changed()` doesn't seem to work on Travis.
-        if option_env!("TRAVIS").is_none() {
-            std::fs::write("tests/temp/dynamic_changed.txt", "Old").unwrap();
+    fn array_progress() {
+        let progress = LoadProgress::new();
+        let [a, _, _] = progress.track(|| {
+            resource!([
+                "tests/bytes_a.bin",
+                "tests/bytes_b.bin",
+                "tests/bytes_c.bin"
+            ])
+        });
+
+        assert_eq!(&*a, b"Bytes A");
+        assert_eq!(progress.files_done(), 3);
+        assert_eq!(progress.files_total(), 3);
+        assert_eq!(progress.bytes_done(), 21);
+        assert_eq!(progress.bytes_total(), 21);
+        assert_eq!(progress.fraction(), 1.0);
+    }
 
-            let res = resource_str!("tests/temp/dynamic_changed.txt");
-            assert!(!res.changed());
+    #[test]
+    fn list_progress_with_callback() {
+        let updates = Arc::new(AtomicUsize::new(0));
+        let progress = LoadProgress::with_callback({
+            let updates = Arc::clone(&updates);
+            move |progress| {
+                assert!(progress.files_done() <= progress.files_total());
+                updates.fetch_add(1, Ordering::Relaxed);
+            }
+        });
 
-            std::fs::write("tests/temp/dynamic_changed.txt", "New").unwrap();
-            assert!(res.changed());
-        }
+        let files = progress.track(|| resource_str_list!("tests/resource_list_test_files"));
+
+        assert_eq!(progress.files_done(), files.len());
+        assert!(progress.is_complete());
+        assert!(updates.load(Ordering::Relaxed) >= 1);
     }
 
     #[test]
-    fn reload() {
-        std::fs::write("tests/temp/dynamic_reload.txt", "Old").unwrap();
-
-        let mut res = resource_str!("tests/temp/dynamic_reload.txt");
-        std::fs::write("tests/temp/dynamic_reload.txt", "New").unwrap();
+    fn open_all_progress() {
+        let progress = LoadProgress::new();
+        let result = progress
+            .track(|| Resource::<[u8]>::open_all(&["tests/bytes_a.bin", "tests/missing.bin"]));
+
+        assert!(result.is_err());
+        assert_eq!(progress.files_done(), 2);
+        assert_eq!(progress.bytes_total(), 7);
+    }
 
-        assert_eq!(res.as_ref(), "Old");
-        res.reload();
-        assert_eq!(res.as_ref(), "New");
+    #[test]
+    fn only_tracked_loads_count() {
+        let outer = LoadProgress::new();
+        let inner = LoadProgress::new();
+
+        outer.track(|| {
+            inner.track(|| resource!(["tests/bytes_a.bin"]));
+            resource_str!(["tests/string_a.txt", "tests/string_b.txt"]);
+        });
+        resource!(["tests/bytes_b.bin"]);
+
+        assert_eq!(inner.files_done(), 1);
+        assert_eq!(outer.files_done(), 2);
+        assert_eq!(outer.bytes_done(), 18);
     }
 
     #[test]
-    fn reload_if_changed() {
-        // For some reason, `changed()` doesn't seem to work on Travis.
-        if option_env!("TRAVIS").is_none() {
-            std::fs::write("tests/temp/dynamic_reload_if_changed.txt", "Old").unwrap();
+    fn not_complete_before_tracking() {
+        let progress = LoadProgress::new();
 
-            let mut res = resource_str!("tests/temp/dynamic_reload_if_changed.txt");
-            assert!(!res.reload_if_changed());
-            assert_eq!(res.as_ref(), "Old");
+        assert!(!progress.is_started());
+        assert!(!progress.is_complete());
+        assert_eq!(progress.fraction(), 0.0);
 
-            std::fs::write("tests/temp/dynamic_reload_if_changed.txt", "New").unwrap();
-            assert!(res.reload_if_changed());
-            assert_eq!(res.as_ref(), "New");
-        }
-    }
-}
+        progress.track(|| ());
 
-#[cfg(test)]
-mod resource_list_tests {
-    use super::*;
+        assert!(progress.is_started());
+        assert!(progress.is_complete());
+        assert_eq!(progress.fraction(), 1.0);
+    }
 
     #[test]
-    fn test_resource_str_list() {
-        let files = resource_str_list!("tests/resource_list_test_files");
-        let names = ["file_a.txt", "file_b.txt", "file_c.txt"];
-        let contents = ["A\n", "B\n", "C\n"];
-        for i in 0..files.len() {
-            assert_eq!(files[i].0, names[i]);
-            assert_eq!(files[i].1.as_ref(), contents[i]);
-        }
+    fn not_complete_while_tracking() {
+        let progress = LoadProgress::new();
+        progress.track(|| {
+            resource!(["tests/bytes_a.bin"]);
+
+            assert_eq!(progress.files_done(), progress.files_total());
+            assert!(!progress.is_complete());
+        });
+
+        assert!(progress.is_complete());
     }
 
     #[test]
-    fn test_resource_list() {
-        let files = resource_list!("tests/resource_list_test_files");
-        let names = ["file_a.txt", "file_b.txt", "file_c.txt"];
-        let contents = [b"A\n", b"B\n", b"C\n"];
-        for i in 0..files.len() {
-            assert_eq!(files[i].0, names[i]);
-            assert_eq!(files[i].1.as_ref(), contents[i]);
-        }
+    fn expected_files() {
+        let progress = LoadProgress::new();
+        progress.expect_files(4);
+
+        progress.track(|| resource!(["tests/bytes_a.bin", "tests/bytes_b.bin"]));
+
+        assert_eq!(progress.files_done(), 2);
+        assert_eq!(progress.files_total(), 4);
+        assert_eq!(progress.fraction(), 0.5);
+        assert!(!progress.is_complete());
+
+        progress.track(|| resource!(["tests/bytes_c.bin", "tests/bytes.bin"]));
+
+        assert_eq!(progress.files_total(), 4);
+        assert_eq!(progress.fraction(), 1.0);
+        assert!(progress.is_complete());
     }
 }
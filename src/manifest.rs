@@ -0,0 +1,135 @@
+use std::fmt::Write as _;
+
+/// A file embedded into the binary by one of the macros, and where it was
+/// embedded.
+///
+/// Only files embedded in release mode are recorded, as in debug mode files
+/// are loaded from disk instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ManifestEntry {
+    /// The package the path is relative to.
+    pub package: &'static str,
+
+    /// The path of the file as passed to the macro, separated by `/`. Files
+    /// generated by a build script are prefixed with `$OUT_DIR/`.
+    pub path: &'static str,
+
+    /// The size of the file in bytes.
+    pub size: u64,
+
+    /// The 64-bit FNV-1a hash of the contents of the file, the same as in
+    /// `build::Asset`.
+    pub hash: u64,
+
+    /// The source file containing the macro call.
+    pub file: &'static str,
+
+    /// The line of the macro call.
+    pub line: u32,
+
+    /// The column of the macro call.
+    pub column: u32,
+}
+
+impl ManifestEntry {
+    #[doc(hidden)]
+    pub const fn _new(
+        (package, path, size, hash, file, line, column): (
+            &'static str,
+            &'static str,
+            u64,
+            u64,
+            &'static str,
+            u32,
+            u32,
+        ),
+    ) -> Self {
+        ManifestEntry {
+            package,
+            path,
+            size,
+            hash,
+            file,
+            line,
+            column,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{ \"package\": {}, \"path\": {}, \"size\": {}, \"hash\": \"{:016x}\", \"file\": {}, \"line\": {}, \"column\": {} }}",
+            json_string(self.package),
+            json_string(self.path),
+            self.size,
+            self.hash,
+            json_string(self.file),
+            self.line,
+            self.column
+        )
+    }
+}
+
+inventory::collect!(ManifestEntry);
+
+/// Returns every file embedded into the binary, sorted by package, path and
+/// call site.
+///
+/// A file embedded in several places has one entry for each. In debug mode,
+/// nothing is embedded, so this is empty.
+///
+/// To also write the manifest while compiling, set the `RESOURCE_MANIFEST_DIR`
+/// environment variable. Each call site is written to its own JSON file in
+/// that directory, with the same fields as `manifest_json`. Only crates which
+/// are recompiled are written, so start from a clean build.
+///
+/// Requires the `manifest` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// let size: u64 = resource::manifest().iter().map(|entry| entry.size).sum();
+/// println!("{} bytes of embedded assets", size);
+/// ```
+pub fn manifest() -> Vec<&'static ManifestEntry> {
+    let mut entries: Vec<_> = inventory::iter::<ManifestEntry>.into_iter().collect();
+    entries.sort_by_key(|entry| {
+        (
+            entry.package,
+            entry.path,
+            entry.file,
+            entry.line,
+            entry.column,
+        )
+    });
+    entries
+}
+
+/// Returns the manifest of embedded files as a JSON array.
+///
+/// Requires the `manifest` feature.
+pub fn manifest_json() -> String {
+    let mut json = "[".to_owned();
+    for (i, entry) in manifest().iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        let _ = write!(json, "{}\n  {}", separator, entry.to_json());
+    }
+    json.push_str("\n]\n");
+    json
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
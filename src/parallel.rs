@@ -0,0 +1,155 @@
+use std::{
+    borrow::ToOwned,
+    error::Error,
+    fmt, io,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    thread,
+};
+
+use crate::{LoadProgress, ReadFromFile, Resource};
+
+/// Applies `f` to each item on scoped threads, returning the results in the
+/// same order as the items.
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    if items.len() <= 1 || threads == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let f = &f;
+        let chunks: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+
+        chunks
+            .into_iter()
+            .flat_map(|chunk| match chunk.join() {
+                Ok(results) => results,
+                Err(payload) => std::panic::resume_unwind(payload),
+            })
+            .collect()
+    })
+}
+
+/// Loads each file on scoped threads, returning the results in the same order
+/// as the paths, and reporting to the `LoadProgress` tracked on this thread.
+pub(crate) fn load_all<P, R, F>(paths: &[P], load: F) -> Vec<R>
+where
+    P: AsRef<Path> + Sync,
+    R: Send,
+    F: Fn(&Path) -> R + Sync,
+{
+    let Some(progress) = LoadProgress::current() else {
+        return map(paths, |path| load(path.as_ref()));
+    };
+
+    progress.start(paths.len());
+    map(paths, |path| {
+        let path = path.as_ref();
+        let size = std::fs::metadata(path).map_or(0, |metadata| metadata.len());
+        progress.size_file(size);
+
+        let result = load(path);
+        progress.finish_file(size);
+        result
+    })
+}
+
+/// The files which couldn't be loaded by `Resource::open_all`, or by the `try`
+/// forms of the array and list macros.
+#[derive(Debug)]
+pub struct LoadErrors {
+    errors: Vec<(PathBuf, io::Error)>,
+}
+
+impl LoadErrors {
+    /// Collects the errors from a list of results, returning the loaded
+    /// values if there were none.
+    pub(crate) fn collect<T>(
+        results: impl IntoIterator<Item = (PathBuf, io::Result<T>)>,
+    ) -> Result<Vec<T>, Self> {
+        let mut values = vec![];
+        let mut errors = vec![];
+        for (path, result) in results {
+            match result {
+                Ok(value) => values.push(value),
+                Err(e) => errors.push((path, e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(LoadErrors { errors })
+        }
+    }
+
+    /// Returns each file which couldn't be loaded, along with the reason, in
+    /// the order they were requested.
+    pub fn errors(&self) -> &[(PathBuf, io::Error)] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for LoadErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.errors[..] {
+            [(path, e)] => write!(f, "failed to read `{}`: {}", path.display(), e),
+            errors => {
+                write!(f, "failed to read {} files:", errors.len())?;
+                for (path, e) in errors {
+                    write!(f, "\n    `{}`: {}", path.display(), e)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for LoadErrors {}
+
+impl<B> Resource<B>
+where
+    B: 'static + ToOwned + Sync + ?Sized,
+    B::Owned: ReadFromFile + Send + Sync,
+{
+    /// Loads several files at runtime in parallel, returning the resources in
+    /// the same order as the paths.
+    ///
+    /// Every file is attempted, so if any fail, the error lists all of them
+    /// rather than only the first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use resource::Resource;
+    ///
+    /// let files = Resource::<str>::open_all(&["Cargo.toml", "src/lib.rs"]).unwrap();
+    /// assert!(files[0].contains("[package]"));
+    ///
+    /// let errors = Resource::<str>::open_all(&["missing.txt", "Cargo.toml", "missing.bin"])
+    ///     .unwrap_err();
+    /// assert_eq!(errors.errors().len(), 2);
+    /// ```
+    pub fn open_all<P>(paths: &[P]) -> Result<Vec<Self>, LoadErrors>
+    where
+        P: AsRef<Path> + Sync,
+    {
+        let results = load_all(paths, |path| Resource::open(path));
+        LoadErrors::collect(
+            paths
+                .iter()
+                .map(|path| path.as_ref().to_owned())
+                .zip(results),
+        )
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+};
+
+thread_local! {
+    static CURRENT: RefCell<Option<LoadProgress>> = const { RefCell::new(None) };
+}
+
+type Callback = Box<dyn Fn(&LoadProgress) + Send + Sync>;
+
+/// The progress of loading resources, for showing on a loading screen.
+///
+/// Resources loaded by the list macros, the array forms of `resource!` and
+/// `resource_str!`, and `Resource::open_all` are counted while inside
+/// `track`. In debug mode, the file totals are added when each bulk load
+/// starts, and the files are sized and counted as they are read. In release
+/// mode, embedded files are counted as done straight away.
+///
+/// Loading is complete once `track` has returned, so nothing is complete
+/// before it starts. If the loads are split over several calls to `track`,
+/// or several bulk loads, declare the number of files up front with
+/// `expect_files`, so that progress doesn't reach the end early.
+///
+/// It can be cloned and polled from another thread, or given a callback
+/// which runs after each update.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::{resource_list, LoadProgress};
+///
+/// let progress = LoadProgress::new();
+/// let loader = {
+///     let progress = progress.clone();
+///     std::thread::spawn(move || {
+///         progress.track(|| resource_list!("tests/resource_list_test_files"))
+///     })
+/// };
+///
+/// while !progress.is_complete() {
+///     // Draw a loading screen using `progress.fraction()`
+///     std::thread::yield_now();
+/// }
+///
+/// let files = loader.join().unwrap();
+/// assert_eq!(progress.files_done(), files.len());
+/// assert_eq!(progress.fraction(), 1.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct LoadProgress(Arc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    started: AtomicBool,
+    tracking: AtomicUsize,
+    files_expected: AtomicUsize,
+    files_done: AtomicUsize,
+    files_sized: AtomicUsize,
+    files_total: AtomicUsize,
+    bytes_done: AtomicU64,
+    bytes_total: AtomicU64,
+    callback: Option<Callback>,
+}
+
+impl LoadProgress {
+    /// Creates a progress with nothing to load yet.
+    pub fn new() -> Self {
+        LoadProgress::default()
+    }
+
+    /// Creates a progress which calls `callback` after each update. The
+    /// callback may run on any thread, including the loading threads.
+    pub fn with_callback<F>(callback: F) -> Self
+    where
+        F: Fn(&LoadProgress) + Send + Sync + 'static,
+    {
+        LoadProgress(Arc::new(Inner {
+            callback: Some(Box::new(callback)),
+            ..Inner::default()
+        }))
+    }
+
+    /// Declares how many files will be loaded in total, so that `fraction`
+    /// and `is_complete` account for bulk loads which haven't started yet.
+    pub fn expect_files(&self, files: usize) {
+        self.0.files_expected.store(files, Ordering::Release);
+        self.notify();
+    }
+
+    /// Runs `f`, counting the resources it loads on this thread.
+    pub fn track<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(LoadProgress, Option<LoadProgress>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.1.take());
+                self.0.0.tracking.fetch_sub(1, Ordering::AcqRel);
+                self.0.notify();
+            }
+        }
+
+        // Counted as tracking before being marked as started, so that it
+        // can't look complete in between
+        self.0.tracking.fetch_add(1, Ordering::AcqRel);
+        self.0.started.store(true, Ordering::Release);
+
+        let previous = CURRENT.with(|current| current.borrow_mut().replace(self.clone()));
+        let _restore = Restore(self.clone(), previous);
+        f()
+    }
+
+    /// Returns `true` once `track` has been called.
+    pub fn is_started(&self) -> bool {
+        self.0.started.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of files which have been loaded, or which failed to
+    /// load.
+    pub fn files_done(&self) -> usize {
+        self.0.files_done.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of files which have started loading, or the
+    /// number declared with `expect_files`, if that is more.
+    pub fn files_total(&self) -> usize {
+        self.0
+            .files_total
+            .load(Ordering::Acquire)
+            .max(self.0.files_expected.load(Ordering::Acquire))
+    }
+
+    /// Returns the size in bytes of the files in `files_done`.
+    pub fn bytes_done(&self) -> u64 {
+        self.0.bytes_done.load(Ordering::Acquire)
+    }
+
+    /// Returns the size in bytes of the files which have started loading and
+    /// been sized so far.
+    pub fn bytes_total(&self) -> u64 {
+        self.0.bytes_total.load(Ordering::Acquire)
+    }
+
+    /// Returns the progress from 0 to 1. This is 0 before `track` is called,
+    /// and 1 once loading is complete.
+    ///
+    /// It is by bytes once the sizes of all the files are known, or
+    /// otherwise by files.
+    pub fn fraction(&self) -> f32 {
+        if self.is_complete() {
+            return 1.0;
+        }
+
+        let files_total = self.files_total();
+        let sized = self.0.files_sized.load(Ordering::Acquire) >= files_total;
+        let (done, total) = match self.bytes_total() {
+            bytes_total if sized && bytes_total > 0 => {
+                (self.bytes_done() as f64, bytes_total as f64)
+            }
+            _ => (self.files_done() as f64, files_total as f64),
+        };
+        if total == 0.0 {
+            0.0
+        } else {
+            (done / total).min(1.0) as f32
+        }
+    }
+
+    /// Returns `true` once `track` has returned and every file is done,
+    /// including those declared with `expect_files`.
+    pub fn is_complete(&self) -> bool {
+        self.is_started()
+            && self.0.tracking.load(Ordering::Acquire) == 0
+            && self.files_done() >= self.files_total()
+    }
+
+    fn notify(&self) {
+        if let Some(callback) = &self.0.callback {
+            callback(self);
+        }
+    }
+
+    pub(crate) fn start(&self, files: usize) {
+        self.0.files_total.fetch_add(files, Ordering::AcqRel);
+        self.notify();
+    }
+
+    pub(crate) fn size_file(&self, bytes: u64) {
+        self.0.bytes_total.fetch_add(bytes, Ordering::AcqRel);
+        self.0.files_sized.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(crate) fn finish_file(&self, bytes: u64) {
+        self.0.bytes_done.fetch_add(bytes, Ordering::AcqRel);
+        self.0.files_done.fetch_add(1, Ordering::AcqRel);
+        self.notify();
+    }
+
+    /// Returns the progress being tracked on this thread, if any.
+    pub(crate) fn current() -> Option<LoadProgress> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    #[doc(hidden)]
+    /// Please don't call this directly. It has to be public for the macro
+    /// but you shouldn't call it because it's not stable.
+    pub fn _report_embedded(sizes: impl IntoIterator<Item = usize>) {
+        let Some(progress) = LoadProgress::current() else {
+            return;
+        };
+
+        let (files, bytes) = sizes.into_iter().fold((0, 0), |(files, bytes), size| {
+            (files + 1, bytes + size as u64)
+        });
+        for counter in [
+            &progress.0.files_total,
+            &progress.0.files_sized,
+            &progress.0.files_done,
+        ] {
+            counter.fetch_add(files, Ordering::AcqRel);
+        }
+        for counter in [&progress.0.bytes_total, &progress.0.bytes_done] {
+            counter.fetch_add(bytes, Ordering::AcqRel);
+        }
+        progress.notify();
+    }
+}
+
+impl fmt::Debug for LoadProgress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LoadProgress")
+            .field("files_done", &self.files_done())
+            .field("files_total", &self.files_total())
+            .field("bytes_done", &self.bytes_done())
+            .field("bytes_total", &self.bytes_total())
+            .field("complete", &self.is_complete())
+            .finish()
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+/// A seekable reader over the contents of a resource.
+///
+/// It either reads data already in memory, such as embedded data or a loaded
+/// `Resource`, or streams a file without reading all of it up front.
+///
+/// Returned by `Resource::reader` and the `resource_reader!` macro.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::{Read, Seek, SeekFrom};
+///
+/// use resource::resource;
+///
+/// let toml = resource!("Cargo.toml");
+/// let mut reader = toml.reader();
+///
+/// let mut header = [0; 9];
+/// reader.seek(SeekFrom::Start(0)).unwrap();
+/// reader.read_exact(&mut header).unwrap();
+/// assert_eq!(&header, b"[package]");
+/// ```
+#[derive(Debug)]
+pub struct ResourceReader<'a>(Inner<'a>);
+
+#[derive(Debug)]
+enum Inner<'a> {
+    Memory(Cursor<&'a [u8]>),
+    File(BufReader<File>),
+}
+
+impl<'a> ResourceReader<'a> {
+    /// Creates a reader over data in memory.
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        ResourceReader(Inner::Memory(Cursor::new(bytes)))
+    }
+
+    /// Returns `true` if the reader streams a file, rather than reading data
+    /// in memory.
+    pub fn is_file(&self) -> bool {
+        matches!(self.0, Inner::File(_))
+    }
+}
+
+impl ResourceReader<'static> {
+    /// Opens a file to stream it, in both debug and release mode.
+    ///
+    /// Nothing is read until the reader is used.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(ResourceReader(Inner::File(BufReader::new(file))))
+    }
+}
+
+impl Read for ResourceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            Inner::Memory(cursor) => cursor.read(buf),
+            Inner::File(file) => file.read(buf),
+        }
+    }
+}
+
+impl BufRead for ResourceReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match &mut self.0 {
+            Inner::Memory(cursor) => cursor.fill_buf(),
+            Inner::File(file) => file.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        match &mut self.0 {
+            Inner::Memory(cursor) => cursor.consume(amount),
+            Inner::File(file) => file.consume(amount),
+        }
+    }
+}
+
+impl Seek for ResourceReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match &mut self.0 {
+            Inner::Memory(cursor) => cursor.seek(pos),
+            Inner::File(file) => file.seek(pos),
+        }
+    }
+}
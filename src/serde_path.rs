@@ -0,0 +1,76 @@
+//! Serializes a `Resource` as the path of its file rather than its contents.
+//!
+//! Use it with serde's `with` attribute. Deserializing opens the file with
+//! `Resource::open`, so the resource can be reloaded as usual.
+//!
+//! Without this, resources are serialized as their contents, and
+//! deserialized resources hold the contents as static data would.
+//!
+//! Requires the `serde` feature.
+//!
+//! ```rust,ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "resource::serde_path")]
+//!     font: Resource<[u8]>,
+//! }
+//! ```
+
+use std::{
+    borrow::{Borrow, ToOwned},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
+
+use crate::{ReadFromFile, Resource, shared::Data};
+
+/// Serializes the path of the resource's file.
+///
+/// Fails for resources created with `Resource::from_static`, which have no
+/// path.
+pub fn serialize<B, S>(resource: &Resource<B>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    B: 'static + ToOwned + ?Sized,
+    S: Serializer,
+{
+    match resource.path() {
+        Some(path) => path.serialize(serializer),
+        None => Err(ser::Error::custom("resource was not loaded from a file")),
+    }
+}
+
+/// Deserializes a path and loads the resource from it.
+pub fn deserialize<'de, B, D>(deserializer: D) -> Result<Resource<B>, D::Error>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: ReadFromFile,
+    D: Deserializer<'de>,
+{
+    let path = PathBuf::deserialize(deserializer)?;
+    Resource::open(&path)
+        .map_err(|e| de::Error::custom(format!("failed to read `{}`: {}", path.display(), e)))
+}
+
+impl<B> Serialize for Resource<B>
+where
+    B: 'static + ToOwned + Serialize + ?Sized,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (*self.data).serialize(serializer)
+    }
+}
+
+impl<'de, B> Deserialize<'de> for Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = B::Owned::deserialize(deserializer)?;
+        let size = std::mem::size_of_val::<B>(data.borrow());
+        Ok(Resource {
+            data: Data::decoded(data, size, None),
+        })
+    }
+}
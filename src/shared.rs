@@ -0,0 +1,248 @@
+use std::{
+    borrow::{Borrow, Cow, ToOwned},
+    ops::Deref,
+    path::Path,
+    sync::Arc,
+};
+
+use crate::file::FileState;
+
+/// The data of a resource. Static and embedded data is borrowed, and loaded
+/// data is reference counted along with where it came from, so that cloning a
+/// resource never copies it and the resource itself stays small.
+pub(crate) enum Data<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    /// Data passed to `Resource::from_static`.
+    Static(&'static B),
+
+    /// Data embedded by the macros in release mode.
+    Embedded(&'static Embedded<B>),
+
+    /// Data loaded from a file, or decoded or deserialized at runtime.
+    Loaded(Arc<Loaded<B>>),
+}
+
+/// Data embedded by the macros in release mode, along with the path it was
+/// embedded from, relative to the crate root. The macros put it in a `static`,
+/// so that a resource only refers to it.
+#[doc(hidden)]
+pub struct Embedded<B>
+where
+    B: 'static + ?Sized,
+{
+    data: &'static B,
+    source: &'static str,
+}
+
+impl<B> Embedded<B>
+where
+    B: 'static + ?Sized,
+{
+    #[doc(hidden)]
+    /// Please don't call this directly. It has to be public for the macro
+    /// but you shouldn't call it because it's not stable.
+    pub const fn _new(data: &'static B, source: &'static str) -> Self {
+        Embedded { data, source }
+    }
+}
+
+pub(crate) struct Loaded<B>
+where
+    B: ToOwned + ?Sized,
+{
+    data: B::Owned,
+
+    /// The size in bytes of the file the data was read or decoded from, or
+    /// of the data itself if it didn't come from a file.
+    size: usize,
+
+    /// The file the data was loaded from, if it can be reloaded.
+    file: Option<FileState>,
+
+    /// The path of the embedded file the data was decoded from in release
+    /// mode, relative to the crate root.
+    source: Option<&'static str>,
+}
+
+impl<B> Data<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    pub fn from_file(data: B::Owned, size: usize, file: FileState) -> Self {
+        Data::Loaded(Arc::new(Loaded {
+            data,
+            size,
+            file: Some(file),
+            source: None,
+        }))
+    }
+
+    /// Data decoded or deserialized at runtime, which can't be reloaded.
+    #[cfg(any(
+        feature = "serde",
+        feature = "force-static",
+        all(not(feature = "force-dynamic"), not(debug_assertions))
+    ))]
+    pub fn decoded(data: B::Owned, size: usize, source: Option<&'static str>) -> Self {
+        Data::Loaded(Arc::new(Loaded {
+            data,
+            size,
+            file: None,
+            source,
+        }))
+    }
+
+    /// Returns the file the data was loaded from, if it can be reloaded.
+    pub fn file(&self) -> Option<&FileState> {
+        match self {
+            Data::Loaded(loaded) => loaded.file.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the path of the file the data was loaded or embedded from.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Data::Static(_) => None,
+            Data::Embedded(embedded) => Some(Path::new(embedded.source)),
+            Data::Loaded(loaded) => match &loaded.file {
+                Some(file) => Some(&file.path),
+                None => loaded.source.map(Path::new),
+            },
+        }
+    }
+
+    /// Returns the size in bytes of the file the data was loaded, decoded or
+    /// embedded from, or of the data itself if it didn't come from a file.
+    pub fn size(&self) -> usize {
+        match self {
+            Data::Static(data) => std::mem::size_of_val::<B>(*data),
+            Data::Embedded(embedded) => std::mem::size_of_val::<B>(embedded.data),
+            Data::Loaded(loaded) => loaded.size,
+        }
+    }
+
+    /// Returns the data if it is static or embedded, rather than loaded.
+    pub fn as_static(&self) -> Option<&'static B> {
+        match self {
+            Data::Static(data) => Some(data),
+            Data::Embedded(embedded) => Some(embedded.data),
+            Data::Loaded(_) => None,
+        }
+    }
+
+    /// Takes the loaded data out of the reference count, copying it only if
+    /// it is still shared with a clone.
+    pub fn into_cow(self) -> Cow<'static, B> {
+        match self {
+            Data::Static(data) => Cow::Borrowed(data),
+            Data::Embedded(embedded) => Cow::Borrowed(embedded.data),
+            Data::Loaded(loaded) => Cow::Owned(
+                Arc::try_unwrap(loaded)
+                    .map(|loaded| loaded.data)
+                    .unwrap_or_else(|loaded| loaded.data.borrow().to_owned()),
+            ),
+        }
+    }
+}
+
+impl<B> Deref for Data<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        match self {
+            Data::Static(data) => data,
+            Data::Embedded(embedded) => embedded.data,
+            Data::Loaded(loaded) => loaded.data.borrow(),
+        }
+    }
+}
+
+impl<B> Clone for Data<B>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Data::Static(data) => Data::Static(data),
+            Data::Embedded(embedded) => Data::Embedded(embedded),
+            Data::Loaded(loaded) => Data::Loaded(Arc::clone(loaded)),
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes_impl {
+    use std::{borrow::ToOwned, sync::Arc};
+
+    use bytes::Bytes;
+
+    use super::{Data, Loaded};
+    use crate::Resource;
+
+    /// Keeps loaded data alive for as long as a `Bytes` refers to it.
+    struct Owner<B>(Arc<Loaded<B>>)
+    where
+        B: ToOwned + ?Sized;
+
+    impl<B> AsRef<[u8]> for Owner<B>
+    where
+        B: ToOwned + ?Sized,
+        B::Owned: AsRef<[u8]>,
+    {
+        fn as_ref(&self) -> &[u8] {
+            self.0.data.as_ref()
+        }
+    }
+
+    impl<B> Resource<B>
+    where
+        B: 'static + ToOwned + AsRef<[u8]> + ?Sized,
+        B::Owned: AsRef<[u8]> + Send + Sync,
+    {
+        /// Returns the data as `Bytes`, without copying it.
+        ///
+        /// Static data is borrowed, and loaded data shares its reference
+        /// count with the resource, so it stays valid after the resource is
+        /// reloaded or dropped.
+        ///
+        /// There is no such conversion to `Arc<[u8]>`. An `Arc<[u8]>` must own
+        /// its bytes in its own allocation, but static data is borrowed, and
+        /// loaded data is kept in the same allocation as the state of its
+        /// file, so either would have to be copied. Use `Bytes`, or copy the
+        /// data explicitly with `Arc::from(&*resource)`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use resource::resource;
+        ///
+        /// let toml = resource!("Cargo.toml");
+        /// let bytes = toml.to_bytes();
+        ///
+        /// assert_eq!(bytes.as_ptr(), toml.as_ptr());
+        /// ```
+        pub fn to_bytes(&self) -> Bytes {
+            match &self.data {
+                Data::Static(data) => Bytes::from_static((*data).as_ref()),
+                Data::Embedded(embedded) => Bytes::from_static(embedded.data.as_ref()),
+                Data::Loaded(loaded) => Bytes::from_owner(Owner(Arc::clone(loaded))),
+            }
+        }
+    }
+
+    impl<B> From<Resource<B>> for Bytes
+    where
+        B: 'static + ToOwned + AsRef<[u8]> + ?Sized,
+        B::Owned: AsRef<[u8]> + Send + Sync,
+    {
+        fn from(resource: Resource<B>) -> Self {
+            resource.to_bytes()
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::{borrow::ToOwned, convert::AsRef, error::Error, ops::Deref};
+
+use crate::{ReadFromFile, ReloadError, ReloadOutcome, Resource};
+
+/// A resource along with a value produced from its contents.
+///
+/// Reloading a `Transformed` reloads the underlying resource and then runs
+/// the transformation function again. If either step fails, the previous
+/// value is kept, so a syntax error in a file being edited doesn't take down
+/// the program.
+///
+/// Create one with `Resource::transform`.
+///
+/// # Examples
+///
+/// ```rust
+/// use resource::resource_str;
+///
+/// let mut lines = resource_str!("Cargo.toml")
+///     .transform(|s: &str| Ok::<_, String>(s.lines().count()))
+///     .unwrap();
+///
+/// assert!(*lines > 1);
+/// lines.reload_if_changed();
+/// ```
+pub struct Transformed<B, T, E = Box<dyn Error + Send + Sync>>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    resource: Resource<B>,
+    value: T,
+    load_fn: fn(&B) -> Result<T, E>,
+}
+
+impl<B> Resource<B>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: ReadFromFile,
+{
+    /// Applies a fallible transformation to the resource, which will be
+    /// applied again each time it is reloaded.
+    ///
+    /// Returns the error from the transformation function if it fails.
+    pub fn transform<T, E>(self, load_fn: fn(&B) -> Result<T, E>) -> Result<Transformed<B, T, E>, E>
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let value = load_fn(self.as_ref())?;
+        Ok(Transformed {
+            resource: self,
+            value,
+            load_fn,
+        })
+    }
+}
+
+impl<B, T, E> Transformed<B, T, E>
+where
+    B: 'static + ToOwned + ?Sized,
+    B::Owned: ReadFromFile,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    /// Returns the underlying resource.
+    ///
+    /// After a failed transformation, this contains the new data even though
+    /// the transformed value is from the last successful load.
+    pub fn resource(&self) -> &Resource<B> {
+        &self.resource
+    }
+
+    /// Consumes the `Transformed`, returning the transformed value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns `true` if the resource was loaded from a file which has changed
+    /// since loading.
+    pub fn changed(&self) -> bool {
+        self.resource.changed()
+    }
+
+    /// Reloads the resource and transforms it again.
+    ///
+    /// Resources created from static data aren't reloaded, and
+    /// `ReloadOutcome::Unchanged` is returned.
+    pub fn reload(&mut self) -> ReloadOutcome {
+        let outcome = self.resource.reload();
+        self.transform_if_reloaded(outcome)
+    }
+
+    /// Reloads and transforms the resource only if it has changed since the
+    /// previous load.
+    ///
+    /// Resources created from static data aren't reloaded, and
+    /// `ReloadOutcome::Unchanged` is returned.
+    pub fn reload_if_changed(&mut self) -> ReloadOutcome {
+        let outcome = self.resource.reload_if_changed();
+        self.transform_if_reloaded(outcome)
+    }
+
+    fn transform_if_reloaded(&mut self, outcome: ReloadOutcome) -> ReloadOutcome {
+        if !outcome.is_reloaded() {
+            return outcome;
+        }
+
+        match (self.load_fn)(self.resource.as_ref()) {
+            Ok(value) => {
+                self.value = value;
+                ReloadOutcome::Reloaded
+            }
+            Err(e) => ReloadOutcome::Failed(ReloadError::Transform(e.into())),
+        }
+    }
+}
+
+impl<B, T, E> AsRef<T> for Transformed<B, T, E>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<B, T, E> Deref for Transformed<B, T, E>
+where
+    B: 'static + ToOwned + ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<B, T, E> Clone for Transformed<B, T, E>
+where
+    B: 'static + ToOwned + ?Sized,
+    Resource<B>: Clone,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Transformed {
+            resource: self.resource.clone(),
+            value: self.value.clone(),
+            load_fn: self.load_fn,
+        }
+    }
+}
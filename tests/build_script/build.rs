@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+fn main() {
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+
+    std::fs::write(out_dir.join("generated.txt"), "Generated\n").unwrap();
+    std::fs::create_dir_all(out_dir.join("shaders")).unwrap();
+    std::fs::write(out_dir.join("shaders/a.spv"), [1, 2, 3]).unwrap();
+    std::fs::write(out_dir.join("shaders/b.spv"), [4, 5]).unwrap();
+
+    resource::build::AssetManifest::new()
+        .dir("assets")
+        .strict(true)
+        .write()
+        .unwrap();
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,82 @@
+//! Tests for loading files generated by a build script, and for checking
+//! files against the asset manifest written by one, which need a crate with a
+//! build script of its own.
+
+#[cfg(test)]
+mod tests {
+    use resource::{resource, resource_list, resource_str, resource_str_list};
+
+    #[test]
+    fn load_from_out_dir() {
+        let text = resource_str!(out_dir: "generated.txt");
+        let bytes = resource!(out_dir: "shaders/a.spv");
+
+        assert_eq!(&*text, "Generated\n");
+        assert_eq!(&*bytes, [1, 2, 3]);
+    }
+
+    #[test]
+    fn load_declared_asset() {
+        let level = resource_str!("assets/level.txt");
+        assert_eq!(&*level, "Level 1\n");
+
+        let files = resource_str_list!("assets");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "level.txt");
+    }
+
+    #[test]
+    fn manifest_lists_declared_assets() {
+        let manifest = resource_str!(out_dir: "resource_manifest.json");
+
+        assert!(manifest.contains(r#""strict": true"#));
+        assert!(manifest.contains(r#""path": "assets/level.txt""#));
+    }
+
+    #[test]
+    fn load_from_out_dir_with_fn() {
+        let lines = resource_str!(out_dir: "generated.txt", |s: &str| s.lines().count());
+        assert_eq!(lines, 1);
+    }
+
+    #[test]
+    fn list_relative_to_out_dir() {
+        let files = resource_list!("shaders", relative_to = out_dir);
+        let names: Vec<&str> = files.iter().map(|(name, _)| *name).collect();
+
+        assert_eq!(names, ["a.spv", "b.spv"]);
+        assert_eq!(&*files[0].1, [1, 2, 3]);
+        assert_eq!(&*files[1].1, [4, 5]);
+
+        // The asset manifest is written to `OUT_DIR` too
+        let files = resource_str_list!(".", relative_to = out_dir);
+        let names: Vec<&str> = files.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["generated.txt", "resource_manifest.json"]);
+        assert_eq!(&*files[0].1, "Generated\n");
+    }
+
+    #[test]
+    #[cfg(any(
+        feature = "force-dynamic",
+        all(not(feature = "force-static"), debug_assertions)
+    ))]
+    fn out_dir_files_are_read_at_runtime() {
+        let text = resource_str!(out_dir: "generated.txt");
+        let path = text.path().unwrap();
+
+        assert!(path.starts_with(env!("OUT_DIR")));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "Generated\n");
+    }
+
+    #[test]
+    #[cfg(any(
+        feature = "force-static",
+        all(not(feature = "force-dynamic"), not(debug_assertions))
+    ))]
+    fn out_dir_files_are_embedded() {
+        let text = resource_str!(out_dir: "generated.txt");
+
+        assert!(!text.changed());
+        assert!(text.path().unwrap().ends_with("generated.txt"));
+    }
+}